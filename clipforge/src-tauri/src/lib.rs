@@ -27,30 +27,52 @@ struct VideoMetadata {
 }
 
 #[derive(Serialize, Deserialize)]
-struct Clip {
-    id: String,
+struct ClipInfo {
     name: String,
     path: String,
-    start: f64,
-    end: f64,
-    duration: f64,
+    size: u64,
 }
 
-#[derive(Serialize, Deserialize)]
-struct WorkspaceState {
-    clips: Vec<Clip>,
-    playhead: f64,
-    is_playing: bool,
-    zoom: f64,
-    selected_clip_id: Option<String>,
-    export_progress: f64,
+#[derive(Serialize)]
+struct AudioExtractResult {
+    path: String,
+    duration: f64,  // Probed duration of the written file in seconds
 }
 
-#[derive(Serialize, Deserialize)]
-struct ClipInfo {
-    name: String,
+/// A source range to keep, in the shape of ClipExportInfo trims
+#[derive(Serialize)]
+struct TrimSegment {
     path: String,
-    size: u64,
+    trim_start: f64,
+    trim_end: f64,
+}
+
+#[derive(Serialize)]
+struct SplitClipResult {
+    first: VideoMetadata,
+    second: VideoMetadata,
+    first_start: f64,  // Source time where the first file begins (before the in point in fast mode)
+    second_start: f64,  // Source time where the second file begins (before the split point in fast mode)
+}
+
+#[derive(Serialize)]
+struct SilenceReport {
+    silences: Vec<utils::detection::Interval>,  // In source time
+    keep_segments: Vec<TrimSegment>,  // Jump-cut trims with the silences removed
+}
+
+#[derive(Serialize)]
+struct SceneReport {
+    cuts: Vec<utils::detection::SceneCut>,  // In source time
+    segments: Vec<TrimSegment>,  // Sub-clip trims between the cuts
+}
+
+#[derive(Serialize)]
+struct TranscriptionOutput {
+    text: String,
+    segments: Vec<utils::transcription::TranscriptSegment>,
+    vtt_path: String,
+    language: String,
 }
 
 #[tauri::command]
@@ -81,14 +103,79 @@ async fn import_file(file_path: String, app_handle: tauri::AppHandle) -> Result<
     }
 
     // Extract metadata using ffprobe
+    let metadata = probe_video_metadata(&file_path, &app_handle)?;
+
+    // Get app data directory and create clips subdirectory
+    let app_data_dir = app_handle.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let clips_dir = app_data_dir.join("clips");
+    fs::create_dir_all(&clips_dir)
+        .map_err(|e| format!("Failed to create clips directory: {}", e))?;
+
+    // Copy file to clips directory with original filename
+    let file_name = path.file_name()
+        .ok_or("Invalid file path: no filename")?;
+    let dest_path = clips_dir.join(file_name);
+    fs::copy(&file_path, &dest_path)
+        .map_err(|e| format!("Failed to copy file: {}", e))?;
+
+    let dest_path_str = dest_path.to_str()
+        .ok_or("Invalid destination path")?
+        .to_string();
+
+    library_entry(metadata, &dest_path_str, &app_handle)
+}
+
+// Point probed metadata at a file in the clips library and generate its thumbnail
+fn library_entry(
+    metadata: VideoMetadata,
+    dest_path_str: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<VideoMetadata, String> {
+    // Get file size from the destination file
+    let file_size = fs::metadata(dest_path_str)
+        .map_err(|e| format!("Failed to get file metadata: {}", e))?
+        .len();
+
+    // Generate thumbnail automatically
+    let thumbnail_path = match generate_thumbnail(dest_path_str.to_string(), metadata.duration, metadata.width, metadata.height, app_handle.clone()) {
+        Ok(path) => {
+            println!("[Import] ✅ Successfully generated thumbnail: {}", path);
+            Some(path)
+        },
+        Err(e) => {
+            eprintln!("[Import] ⚠️  WARNING: Failed to generate thumbnail for {}: {}", metadata.file_path, e);
+
+            // Emit warning event to frontend for user notification
+            let _ = app_handle.emit("thumbnail-generation-failed", serde_json::json!({
+                "file_path": metadata.file_path,
+                "error": e.to_string()
+            }));
+
+            None  // Continue even if thumbnail generation fails
+        }
+    };
+
+    // Return metadata with new file path and thumbnail
+    Ok(VideoMetadata {
+        file_path: dest_path_str.to_string(),
+        thumbnail_path,
+        file_size,
+        ..metadata
+    })
+}
+
+// Read stream metadata of the first video stream with ffprobe
+fn probe_video_metadata(file_path: &str, app_handle: &tauri::AppHandle) -> Result<VideoMetadata, String> {
     let output = utils::ffmpeg::execute_ffprobe(
-        &app_handle,
+        app_handle,
         &[
             "-v", "error",
             "-select_streams", "v:0",
             "-show_entries", "stream=width,height,duration,codec_name,r_frame_rate,bit_rate",
             "-of", "json",
-            &file_path
+            file_path
         ]
     ).map_err(|e| format!("Failed to run ffprobe: {}", e))?;
 
@@ -132,57 +219,13 @@ async fn import_file(file_path: String, app_handle: tauri::AppHandle) -> Result<
     let bit_rate = stream["bit_rate"].as_str()
         .and_then(|s| s.parse::<u64>().ok());
 
-    // Get app data directory and create clips subdirectory
-    let app_data_dir = app_handle.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    let clips_dir = app_data_dir.join("clips");
-    fs::create_dir_all(&clips_dir)
-        .map_err(|e| format!("Failed to create clips directory: {}", e))?;
-
-    // Copy file to clips directory with original filename
-    let file_name = path.file_name()
-        .ok_or("Invalid file path: no filename")?;
-    let dest_path = clips_dir.join(file_name);
-    fs::copy(&file_path, &dest_path)
-        .map_err(|e| format!("Failed to copy file: {}", e))?;
-
-    // Get file size from the destination file
-    let file_size = fs::metadata(&dest_path)
-        .map_err(|e| format!("Failed to get file metadata: {}", e))?
-        .len();
-
-    let dest_path_str = dest_path.to_str()
-        .ok_or("Invalid destination path")?
-        .to_string();
-
-    // Generate thumbnail automatically
-    let thumbnail_path = match generate_thumbnail(dest_path_str.clone(), duration, width, height, app_handle.clone()) {
-        Ok(path) => {
-            println!("[Import] ✅ Successfully generated thumbnail: {}", path);
-            Some(path)
-        },
-        Err(e) => {
-            eprintln!("[Import] ⚠️  WARNING: Failed to generate thumbnail for {}: {}", file_path, e);
-
-            // Emit warning event to frontend for user notification
-            let _ = app_handle.emit("thumbnail-generation-failed", serde_json::json!({
-                "file_path": file_path,
-                "error": e.to_string()
-            }));
-
-            None  // Continue even if thumbnail generation fails
-        }
-    };
-
-    // Return metadata with new file path and thumbnail
     Ok(VideoMetadata {
         duration,
         width,
         height,
-        file_path: dest_path_str,
-        thumbnail_path,
-        file_size,
+        file_path: file_path.to_string(),
+        thumbnail_path: None,
+        file_size: 0,
         codec,
         fps,
         bit_rate,
@@ -405,6 +448,7 @@ async fn trim_clip(
     output_path: String,
     start_time: f64,
     end_time: f64,
+    mode: Option<utils::trim::TrimMode>,
     app_handle: tauri::AppHandle,
 ) -> Result<String, String> {
     // Validate input file exists
@@ -428,23 +472,297 @@ async fn trim_clip(
             .map_err(|e| format!("Failed to create output directory: {}", e))?;
     }
 
-    // Calculate duration for FFmpeg
-    let duration = end_time - start_time;
+    cut_range(&input_path, start_time, end_time, &output_path, mode.unwrap_or_default(), &app_handle)
+}
 
-    // Use builder for trim operation
-    let result = utils::ffmpeg::FfmpegBuilder::new()
-        .input(&input_path)
-        .trim(start_time, duration)
-        .stream_copy()
-        .output(&output_path)
-        .run(&app_handle);
+#[tauri::command]
+async fn split_clip(
+    input_path: String,
+    trim_start: f64,
+    trim_end: f64,
+    split_time: f64,
+    mode: Option<utils::trim::TrimMode>,
+    app_handle: tauri::AppHandle,
+) -> Result<SplitClipResult, String> {
+    let input = Path::new(&input_path);
+    if !input.exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+    let mode = mode.unwrap_or_default();
+
+    // Only the clip's trimmed range is cut, not the whole source
+    let duration = probe_duration(&input_path, &app_handle)?;
+    if trim_start < 0.0 || trim_end > duration + utils::timeline::GAP_TOLERANCE {
+        return Err(format!(
+            "Trim range {:.3}-{:.3}s is outside the {:.3}s source",
+            trim_start, trim_end, duration
+        ));
+    }
+    let ranges = utils::trim::split_ranges((trim_start, trim_end.min(duration)), split_time)?;
+
+    // Both halves become library entries in the clips directory
+    let app_data_dir = app_handle.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let clips_dir = app_data_dir.join("clips");
+    fs::create_dir_all(&clips_dir)
+        .map_err(|e| format!("Failed to create clips directory: {}", e))?;
+    let stem = input.file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid input filename")?;
+
+    let mut halves: Vec<VideoMetadata> = Vec::new();
+    for (i, (start, end)) in ranges.into_iter().enumerate() {
+        let output = utils::audio_export::unique_output_path(&clips_dir, &format!("{}_part{}", stem, i + 1), "mp4");
+        let output = output.to_str().ok_or("Invalid output path")?.to_string();
+        let half = cut_range(&input_path, start, end, &output, mode, &app_handle)
+            .and_then(|_| probe_video_metadata(&output, &app_handle))
+            .and_then(|metadata| library_entry(metadata, &output, &app_handle));
+
+        match half {
+            Ok(half) => halves.push(half),
+            Err(e) => {
+                // Don't leave half a split behind in the library
+                let _ = fs::remove_file(&output);
+                for half in &halves {
+                    let _ = fs::remove_file(&half.file_path);
+                    if let Some(thumbnail) = &half.thumbnail_path {
+                        let _ = fs::remove_file(thumbnail);
+                    }
+                }
+                return Err(format!("Failed to write part {} of the split: {}", i + 1, e));
+            }
+        }
+    }
+
+    // Stream copy starts each file on the keyframe before its cut point
+    let (first_start, second_start) = match mode {
+        utils::trim::TrimMode::Fast => {
+            let index = load_keyframe_index(&input_path, &app_handle)?;
+            (index.before(trim_start).unwrap_or(0.0), index.before(split_time).unwrap_or(0.0))
+        }
+        _ => (trim_start, split_time),
+    };
+
+    let second = halves.pop().ok_or("Missing second half")?;
+    let first = halves.pop().ok_or("Missing first half")?;
+    Ok(SplitClipResult { first, second, first_start, second_start })
+}
+
+// Write start..end of the input to output_path using the given trim mode
+fn cut_range(
+    input_path: &str,
+    start: f64,
+    end: f64,
+    output_path: &str,
+    mode: utils::trim::TrimMode,
+    app_handle: &tauri::AppHandle,
+) -> Result<String, String> {
+    let result = match mode {
+        utils::trim::TrimMode::Fast => utils::ffmpeg::FfmpegBuilder::new()
+            .input(input_path)
+            .trim(start, end - start)
+            .stream_copy()
+            .output(output_path)
+            .run(app_handle),
+        utils::trim::TrimMode::Reencode => reencode_range(input_path, start, end, output_path, app_handle),
+        utils::trim::TrimMode::Accurate => {
+            return smart_trim(input_path, start, end, output_path, app_handle);
+        }
+    };
 
     match result {
-        Ok(_) => Ok(output_path),
+        Ok(_) => Ok(output_path.to_string()),
         Err(e) => Err(e.to_string()),
     }
 }
 
+// Re-encode start..end of the input (frame accurate)
+fn reencode_range(
+    input_path: &str,
+    start: f64,
+    end: f64,
+    output_path: &str,
+    app_handle: &tauri::AppHandle,
+) -> utils::ffmpeg::FFmpegResult<String> {
+    utils::ffmpeg::FfmpegBuilder::new()
+        .input(input_path)
+        .trim(start, end - start)
+        .encode()
+        .pixel_format("yuv420p")
+        .output(output_path)
+        .run(app_handle)
+}
+
+// Smart render: re-encode the partial GOPs at the cut points with settings matching
+// the source, stream copy the rest and join the pieces with the concat demuxer.
+// Sources libx264 can't match are fully re-encoded.
+fn smart_trim(
+    input_path: &str,
+    start: f64,
+    end: f64,
+    output_path: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<String, String> {
+    let source = probe_source_video(input_path, app_handle)?;
+    let encoder_args = source.matching_encoder_args();
+    let segments = match &encoder_args {
+        Some(_) => {
+            let index = load_keyframe_index(input_path, app_handle)?;
+            utils::trim::plan_smart_cut(&index.keyframes, start, end)
+        }
+        None => {
+            eprintln!("[Trim] {} {} ({}) source can't be smart rendered, re-encoding", source.codec, source.profile, source.pix_fmt);
+            Vec::new()
+        }
+    };
+
+    if !segments.iter().any(|s| s.copy) {
+        return reencode_range(input_path, start, end, output_path, app_handle)
+            .map(|_| output_path.to_string())
+            .map_err(|e| e.to_string());
+    }
+    let encoder_args = encoder_args.unwrap_or_default();
+
+    let output = Path::new(output_path);
+    let parent = output.parent().ok_or("Invalid output path")?;
+    let stem = output.file_stem().and_then(|s| s.to_str()).ok_or("Invalid output filename")?;
+
+    let mut part_paths = Vec::new();
+    let mut result = Ok(());
+    for (i, segment) in segments.iter().enumerate() {
+        let part_path = parent.join(format!("{}.part{}.{}", stem, i, utils::trim::SMART_PART_EXTENSION));
+        let part = part_path.to_str().ok_or("Invalid temp path")?.to_string();
+        part_paths.push(part_path);
+
+        if let Err(e) = utils::trim::segment_builder(input_path, segment, &encoder_args, &part).run(app_handle) {
+            result = Err(format!("Failed to render trim segment {}: {}", i, e));
+            break;
+        }
+    }
+
+    let concat_list_path = parent.join(format!("{}.concat.txt", stem));
+    if result.is_ok() {
+        result = concat_parts(&part_paths, &concat_list_path, &source, output_path, app_handle);
+    }
+
+    // Clean up temp files
+    for part_path in &part_paths {
+        let _ = fs::remove_file(part_path);
+    }
+    let _ = fs::remove_file(&concat_list_path);
+
+    result.map(|_| output_path.to_string())
+}
+
+// Join rendered pieces losslessly with the concat demuxer
+fn concat_parts(
+    part_paths: &[PathBuf],
+    concat_list_path: &Path,
+    source: &utils::trim::SourceVideo,
+    output_path: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    let mut concat_file = fs::File::create(concat_list_path)
+        .map_err(|e| format!("Failed to create concat list file: {}", e))?;
+    for part_path in part_paths {
+        writeln!(concat_file, "file '{}'", part_path.to_str().ok_or("Invalid path")?)
+            .map_err(|e| format!("Failed to write to concat list: {}", e))?;
+    }
+    concat_file.flush()
+        .map_err(|e| format!("Failed to flush concat list: {}", e))?;
+    drop(concat_file);
+
+    utils::trim::join_builder(concat_list_path.to_str().ok_or("Invalid concat list path")?, source, output_path)
+        .run(app_handle)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+// Encoding properties of the first video stream
+fn probe_source_video(file_path: &str, app_handle: &tauri::AppHandle) -> Result<utils::trim::SourceVideo, String> {
+    let output = utils::ffmpeg::execute_ffprobe(
+        app_handle,
+        &[
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-show_entries", utils::trim::SOURCE_VIDEO_ENTRIES,
+            "-of", "default=noprint_wrappers=1",
+            file_path
+        ]
+    ).map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+
+    utils::trim::SourceVideo::parse(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| format!("No video stream found in {}", file_path))
+}
+
+// Load the clip's cached keyframe index, rebuilding it when missing or stale
+fn load_keyframe_index(
+    file_path: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<utils::keyframes::KeyframeIndex, String> {
+    let source = Path::new(file_path);
+    if !source.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+    let stamp = utils::keyframes::source_stamp(source)?;
+
+    // Cached alongside thumbnails so reset_workspace clears both
+    let app_data_dir = app_handle.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let index_dir = app_data_dir.join("clips").join("keyframes");
+    fs::create_dir_all(&index_dir)
+        .map_err(|e| format!("Failed to create keyframes directory: {}", e))?;
+    let cache_path = index_dir.join(utils::keyframes::cache_file_name(file_path));
+
+    if let Some(index) = utils::keyframes::KeyframeIndex::read_cache(&cache_path, file_path, stamp) {
+        return Ok(index);
+    }
+
+    let output = utils::ffmpeg::execute_ffprobe(
+        app_handle,
+        &[
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-show_entries", "packet=pts_time,duration_time,flags",
+            "-of", "csv=p=0",
+            file_path
+        ]
+    ).map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to index keyframes: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let index = utils::keyframes::KeyframeIndex::from_packets(file_path, stamp, &String::from_utf8_lossy(&output.stdout));
+    if let Err(e) = index.write_cache(&cache_path) {
+        eprintln!("[Keyframes] {}", e);
+    }
+    Ok(index)
+}
+
+#[tauri::command]
+async fn get_keyframe_index(
+    file_path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<utils::keyframes::KeyframeIndex, String> {
+    tokio::task::spawn_blocking(move || load_keyframe_index(&file_path, &app_handle))
+        .await
+        .map_err(|e| format!("Keyframe indexing task failed: {}", e))?
+}
+
+#[tauri::command]
+async fn nearest_keyframes(
+    file_path: String,
+    time: f64,
+    app_handle: tauri::AppHandle,
+) -> Result<utils::keyframes::KeyframeNeighbors, String> {
+    let index = tokio::task::spawn_blocking(move || load_keyframe_index(&file_path, &app_handle))
+        .await
+        .map_err(|e| format!("Keyframe indexing task failed: {}", e))??;
+    Ok(index.neighbors(time))
+}
+
 #[tauri::command]
 async fn save_recording(
     file_name: String,
@@ -507,34 +825,275 @@ async fn save_recording(
     }
 }
 
-#[derive(serde::Deserialize)]
+// Render settings for one item of a track sequence
 struct ClipExportInfo {
-    path: String,
+    path: String,  // Source media (unused for title cards)
     trim_start: f64,
     trim_end: f64,
-    volume: Option<f64>,  // Audio volume (0.0-1.0, where 1.0 is 100%)
-    muted: Option<bool>,  // Whether audio is muted
-    vtt_path: Option<String>,  // Path to VTT caption file
+    effects: utils::clip::ClipEffects,  // Volume/fades/grading/speed, rendered from the untouched source
+    vtt_path: Option<String>,  // Path to VTT or SRT caption file
+    title_card: Option<utils::text_overlay::TitleCard>,  // Generated full-screen title instead of media
+    gap: bool,  // Black, silent filler for an empty stretch of the timeline
 }
 
-#[tauri::command]
-async fn export_video(
-    clips: Vec<ClipExportInfo>,
-    output_path: String,
-    resolution: String, // "720p" or "1080p"
-    app_handle: tauri::AppHandle,
-) -> Result<String, String> {
-    // Validate inputs
-    if clips.is_empty() {
-        return Err("No clips provided for export".to_string());
+impl ClipExportInfo {
+    /// Render settings for a timeline clip
+    fn from_clip(clip: &utils::clip::Clip) -> Self {
+        Self {
+            path: clip.path.clone(),
+            trim_start: clip.trim_start,
+            trim_end: clip.trim_end,
+            effects: clip.effects.clone(),
+            vtt_path: clip.captions_path().map(|p| p.to_string()),
+            title_card: clip.title_card.clone(),
+            gap: false,
+        }
     }
 
-    // Validate all input files exist
-    for clip in &clips {
-        let path = Path::new(&clip.path);
-        if !path.exists() {
+    /// Black, silent filler for a gap in the timeline
+    fn gap(duration: f64) -> Self {
+        Self {
+            path: String::new(),
+            trim_start: 0.0,
+            trim_end: duration,
+            effects: utils::clip::ClipEffects::default(),
+            vtt_path: None,
+            title_card: None,
+            gap: true,
+        }
+    }
+
+    /// Whether the clip is rendered from a source file rather than generated
+    fn has_media(&self) -> bool {
+        self.title_card.is_none() && !self.gap
+    }
+
+    /// Length of the clip once rendered (trim range adjusted for speed)
+    fn output_duration(&self) -> f64 {
+        (self.trim_end - self.trim_start) / self.effects.speed_factor()
+    }
+}
+
+/// Program-level export settings applied after the clips are rendered
+#[derive(serde::Deserialize, Default)]
+struct ExportOptions {
+    loudness: Option<utils::loudness::LoudnessTarget>,  // Two-pass loudnorm target
+    #[serde(default)]
+    audio_tracks: Vec<utils::audio_mix::AudioTrackClip>,  // Music/voiceover layered under the video
+    ducking: Option<utils::audio_mix::DuckingSettings>,  // Duck music under dialog
+    #[serde(default)]
+    transitions: Vec<utils::transitions::Transition>,  // Per-boundary transitions (hard cut otherwise)
+    #[serde(default)]
+    text_overlays: Vec<utils::text_overlay::TextOverlay>,  // Titles/lower-thirds on the program timeline
+    watermark: Option<utils::overlay::Watermark>,  // Logo burned over the program
+    layout: Option<utils::composition::ExportLayout>,  // Composite clips into one frame instead of a sequence
+    burn_captions: Option<utils::subtitles::CaptionStyle>,  // Burn each clip's captions into the video
+    #[serde(default)]
+    caption_format: utils::subtitles::CaptionFormat,  // Format of the merged caption sidecar
+    embed_captions: Option<utils::subtitles::EmbeddedCaptions>,  // Also mux captions into the container
+}
+
+impl ExportOptions {
+    /// Whether the rendered program needs a finishing pass before it is final
+    fn needs_finishing(&self) -> bool {
+        self.loudness.is_some()
+            || !self.audio_tracks.is_empty()
+            || !self.text_overlays.is_empty()
+            || self.watermark.is_some()
+    }
+}
+
+// Fresh scratch directory for one export's intermediates, so concurrent exports don't collide
+fn export_temp_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let exports_dir = app_handle.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?
+        .join("exports");
+    fs::create_dir_all(&exports_dir)
+        .map_err(|e| format!("Failed to create export temp directory: {}", e))?;
+
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let mut attempt = 0;
+    loop {
+        let dir = exports_dir.join(format!("{}-{}", stamp, attempt));
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(format!("Failed to create export temp directory: {}", e)),
+        }
+    }
+}
+
+// Validate a timeline against the media on disk, probing each source once
+fn timeline_issues(timeline: &utils::timeline::Timeline, app_handle: &tauri::AppHandle) -> Vec<utils::timeline::TimelineIssue> {
+    let mut durations = std::collections::HashMap::new();
+    for clip in timeline.clips().filter(|c| c.title_card.is_none()) {
+        if !durations.contains_key(&clip.path) {
+            let duration = Path::new(&clip.path).exists()
+                .then(|| probe_duration(&clip.path, app_handle).ok())
+                .flatten();
+            durations.insert(clip.path.clone(), duration);
+        }
+    }
+    timeline.validate(|path| durations.get(path).copied().flatten())
+}
+
+#[tauri::command]
+async fn validate_timeline(
+    clips: Vec<utils::clip::Clip>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<utils::timeline::TimelineIssue>, String> {
+    let timeline = utils::timeline::Timeline::from_clips(clips);
+    Ok(timeline_issues(&timeline, &app_handle))
+}
+
+// Virtual trim: new in/out points on the same source, keeping the timeline start
+#[tauri::command]
+async fn set_clip_trim(
+    clip: utils::clip::Clip,
+    trim_start: f64,
+    trim_end: f64,
+) -> Result<utils::clip::Clip, String> {
+    clip.with_trim(trim_start, trim_end)
+}
+
+// Virtual split at a timeline time; both halves keep referencing the clip's source
+#[tauri::command]
+async fn split_clip_at(
+    clip: utils::clip::Clip,
+    time: f64,
+    second_id: String,
+) -> Result<(utils::clip::Clip, utils::clip::Clip), String> {
+    clip.split_at(time, &second_id)
+}
+
+#[tauri::command]
+async fn export_video(
+    clips: Vec<utils::clip::Clip>,
+    output_path: String,
+    resolution: String, // "720p" or "1080p"
+    options: Option<ExportOptions>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    // Validate inputs
+    if clips.is_empty() {
+        return Err("No clips provided for export".to_string());
+    }
+
+    let timeline = utils::timeline::Timeline::from_clips(clips);
+    let issues = timeline_issues(&timeline, &app_handle);
+    if !issues.is_empty() {
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        return Err(format!("Timeline has problems: {}", messages.join("; ")));
+    }
+
+    // Each track is a sequence with gaps rendered as black, and the tracks are stacked.
+    // Layouts play the clips side by side, so gaps and tracks don't apply.
+    let mut options = options.unwrap_or_default();
+    let (clips, layer_sizes): (Vec<ClipExportInfo>, Vec<usize>) = match &options.layout {
+        Some(_) => {
+            let clips: Vec<ClipExportInfo> = timeline.clips().map(ClipExportInfo::from_clip).collect();
+            let count = clips.len();
+            (clips, vec![count])
+        }
+        None => {
+            let sequences: Vec<_> = timeline.tracks.iter().map(|t| t.sequence(timeline.duration())).collect();
+            if let [sequence] = sequences.as_slice() {
+                options.transitions = utils::timeline::remap_transitions(sequence, &options.transitions)?;
+            } else if !options.transitions.is_empty() {
+                return Err("Transitions are only supported on single-track timelines".to_string());
+            }
+            let layer_sizes = sequences.iter().map(|s| s.len()).collect();
+            let clips = sequences.iter()
+                .flatten()
+                .map(|item| match item {
+                    utils::timeline::SequenceItem::Clip(clip) => ClipExportInfo::from_clip(clip),
+                    utils::timeline::SequenceItem::Gap(duration) => ClipExportInfo::gap(*duration),
+                })
+                .collect();
+            (clips, layer_sizes)
+        }
+    };
+    // The clips of each track, lowest track first
+    let mut layers: Vec<&[ClipExportInfo]> = Vec::new();
+    let mut rest = clips.as_slice();
+    for size in &layer_sizes {
+        let (layer, tail) = rest.split_at(*size);
+        layers.push(layer);
+        rest = tail;
+    }
+
+    // Validate all input files exist
+    for clip in &clips {
+        let path = Path::new(&clip.path);
+        if clip.has_media() && !path.exists() {
             return Err(format!("Clip not found: {}", clip.path));
         }
+        clip.effects.check_reverse(clip.trim_end - clip.trim_start)?;
+        if let Some(lut) = &clip.effects.lut_path {
+            let lut_path = Path::new(lut);
+            if !lut_path.exists() {
+                return Err(format!("LUT file not found: {}", lut));
+            }
+            if lut_path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()) != Some("cube".to_string()) {
+                return Err(format!("Unsupported LUT format: {}. Only .cube files are supported.", lut));
+            }
+        }
+    }
+
+    for track in &options.audio_tracks {
+        if !Path::new(&track.path).exists() {
+            return Err(format!("Audio track not found: {}", track.path));
+        }
+        if track.trim_start < 0.0 || track.trim_start >= track.trim_end {
+            return Err(format!("Invalid trim range for audio track: {}", track.path));
+        }
+    }
+
+    if let Some(watermark) = &options.watermark {
+        let path = Path::new(&watermark.path);
+        if !path.exists() {
+            return Err(format!("Watermark image not found: {}", watermark.path));
+        }
+        if path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()) != Some("png".to_string()) {
+            return Err(format!("Unsupported watermark format: {}. Only PNG files are supported.", watermark.path));
+        }
+    }
+
+    if let Some(style) = &options.burn_captions {
+        style.validate()?;
+        for path in clips.iter().filter_map(|c| c.vtt_path.as_ref()) {
+            if !utils::subtitles::is_supported_caption_file(path) {
+                return Err(format!("Unsupported caption format: {}. Use VTT, SRT or ASS.", path));
+            }
+            if !Path::new(path).exists() {
+                return Err(format!("Caption file not found: {}", path));
+            }
+        }
+    }
+
+    if let Some(settings) = &options.embed_captions {
+        settings.validate()?;
+        settings.codec_for(&output_path)?;
+    }
+
+    for overlay in &options.text_overlays {
+        overlay.validate()?;
+    }
+    for card in clips.iter().filter_map(|c| c.title_card.as_ref()) {
+        card.validate()?;
+    }
+
+    // Fonts are resolved by FFmpeg at render time, so check them up front
+    let font_files = options.text_overlays.iter().filter_map(|o| o.font_file.as_ref())
+        .chain(clips.iter().filter_map(|c| c.title_card.as_ref().and_then(|t| t.font_file.as_ref())));
+    for font in font_files {
+        if !Path::new(font).exists() {
+            return Err(format!("Font file not found: {}", font));
+        }
     }
 
     // Parse resolution
@@ -556,119 +1115,704 @@ async fn export_video(
         _ => return Err(format!("Unsupported resolution: {}. Use 'source', '480p', '720p', '1080p', or '4K'.", resolution)),
     };
 
-    // If single clip, simple re-encode with resolution and trim
-    if clips.len() == 1 {
-        return export_single_clip(&clips[0], &output_path, width, height, &app_handle).await;
+    let durations: Vec<f64> = clips.iter().map(|c| c.output_duration()).collect();
+    utils::transitions::validate_transitions(&durations, &options.transitions)?;
+
+    // Composition layouts play their clips simultaneously rather than in sequence
+    let layout_graph = match &options.layout {
+        Some(layout) => {
+            layout.check_input_count(clips.len())?;
+            if !options.transitions.is_empty() {
+                return Err("Transitions are not supported with composition layouts".to_string());
+            }
+            if clips.iter().any(|c| c.title_card.is_some()) {
+                return Err("Title cards are not supported with composition layouts".to_string());
+            }
+            if options.burn_captions.is_some() {
+                return Err("Burned-in captions are not supported with composition layouts".to_string());
+            }
+            // Layout inputs only get volume/gain; anything else would be silently dropped
+            for clip in &clips {
+                let effects = clip.effects.filter_effects();
+                if !effects.is_empty() {
+                    return Err(format!(
+                        "Composition layouts don't support per-clip {} ({})",
+                        effects.join(", "), clip.path
+                    ));
+                }
+            }
+
+            let inputs: Vec<utils::composition::LayoutInput> = clips.iter()
+                .map(|clip| utils::composition::LayoutInput {
+                    duration: clip.output_duration(),
+                    has_audio: probe_has_audio(&clip.path, &app_handle),
+                    gain: clip.effects.linear_gain(),
+                })
+                .collect();
+            Some(layout.build_graph(width, height, &inputs))
+        }
+        None => None,
+    };
+
+    let total_duration = match &layout_graph {
+        Some(graph) => graph.duration,
+        None if layers.len() > 1 => timeline.duration(),
+        None => utils::transitions::timeline_duration(&durations, &options.transitions),
+    };
+
+    // Render the program straight to the output unless a finishing pass follows
+    let temp_dir = export_temp_dir(&app_handle)?;
+    let finishing = options.needs_finishing();
+    let program_path = if finishing {
+        temp_dir.join("program.mp4").to_str().ok_or("Invalid temp path")?.to_string()
+    } else {
+        output_path.clone()
+    };
+    let (program_range, finishing_range) = if finishing { (85, 15) } else { (100, 0) };
+
+    let result = if let Some(graph) = &layout_graph {
+        // Composition: all clips in one filter graph
+        export_layout(&clips, graph, &program_path, &app_handle, 0, program_range).await
+    } else if layers.len() > 1 {
+        // Tracks: each rendered as its own sequence, then stacked
+        let spans: Vec<Vec<(f64, f64)>> = timeline.tracks.iter().map(|t| t.spans()).collect();
+        export_tracks(&layers, &spans, options.burn_captions.as_ref(), &program_path, &temp_dir, width, height, total_duration, &app_handle, 0, program_range).await
+    } else if clips.len() == 1 {
+        // If single clip, simple re-encode with resolution and trim
+        export_single_clip(&clips[0], options.burn_captions.as_ref(), &program_path, width, height, &app_handle, 0, program_range).await
+    } else {
+        // Multi-clip: use concat demuxer (or transition graph) with trims
+        export_multi_clips(&clips, &options.transitions, options.burn_captions.as_ref(), &program_path, &temp_dir, width, height, &app_handle, 0, program_range).await
+    };
+
+    let result = match result {
+        Ok(_) if finishing => {
+            finish_export(&program_path, &output_path, &options, total_duration, width, &app_handle, program_range, finishing_range).await
+        }
+        result => result,
+    };
+    let _ = fs::remove_dir_all(&temp_dir);
+    result?;
+
+    // Layout clips play simultaneously, so their captions all start at 0; each track starts at 0
+    let offsets: Vec<f64> = match &layout_graph {
+        Some(_) => vec![0.0; clips.len()],
+        None => layers.iter()
+            .flat_map(|layer| {
+                let durations: Vec<f64> = layer.iter().map(|c| c.output_duration()).collect();
+                utils::transitions::clip_offsets(&durations, &options.transitions)
+            })
+            .collect(),
+    };
+    let captions = collect_captions(&clips, &offsets, total_duration);
+    if !captions.is_empty() {
+        write_caption_sidecar(&captions, options.caption_format, &output_path);
+        if let Some(settings) = options.embed_captions.clone() {
+            // The remux runs synchronously, so keep it off the async runtime
+            let (output, handle) = (output_path.clone(), app_handle.clone());
+            let embedded = tokio::task::spawn_blocking(move || embed_captions(&captions, &settings, &output, &handle))
+                .await
+                .map_err(|e| format!("Caption embedding task failed: {}", e))
+                .and_then(|result| result);
+            // Don't leave an export behind that silently lacks the requested captions
+            if let Err(e) = embedded {
+                let _ = fs::remove_file(&output_path);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(output_path)
+}
+
+// Helper function for the program-level finishing pass (overlays, audio mixing, loudness normalization)
+#[allow(clippy::too_many_arguments)]
+async fn finish_export(
+    program_path: &str,
+    output_path: &str,
+    options: &ExportOptions,
+    duration: f64,
+    width: u32,
+    app_handle: &tauri::AppHandle,
+    progress_offset: u32,
+    progress_range: u32,
+) -> Result<String, String> {
+    let probe_path = program_path.to_string();
+    let probe_handle = app_handle.clone();
+    let program_has_audio = tokio::task::spawn_blocking(move || probe_has_audio(&probe_path, &probe_handle))
+        .await
+        .map_err(|e| format!("Audio probe task failed: {}", e))?;
+
+    // Measure the final mix so normalization accounts for the layered tracks
+    let mut normalize_filter = None;
+    if let Some(target) = &options.loudness {
+        let measure_graph = finishing_audio_graph(options, duration, program_has_audio, Some(&target.measure_filter()));
+
+        if let Some(graph) = measure_graph {
+            let measure_builder = finishing_builder(program_path, options)
+                .filter_complex(&graph)
+                .map(utils::audio_mix::MIX_OUTPUT_LABEL);
+            let measure_handle = app_handle.clone();
+            let measured = tokio::task::spawn_blocking(move || run_loudness_measurement(measure_builder, &measure_handle))
+                .await
+                .map_err(|e| format!("Loudness measurement task failed: {}", e))??;
+
+            if measured.is_measurable() {
+                println!("[Export] Program loudness: {:.2} LUFS, normalizing to {:.2} LUFS", measured.integrated, target.integrated);
+                normalize_filter = Some(target.normalize_filter(&measured));
+            }
+        }
+
+        if normalize_filter.is_none() {
+            println!("[Export] Program audio is silent, skipping loudness normalization");
+        }
+    }
+
+    let mut graph = Vec::new();
+    let mut builder = finishing_builder(program_path, options);
+
+    // Video is only re-encoded when something is drawn over it
+    match finishing_video_graph(options, width) {
+        Some(video_graph) => {
+            graph.push(video_graph);
+            builder = builder.map("[vout]").encode().pixel_format("yuv420p");
+        }
+        None => builder = builder.map("0:v").copy_video(),
+    }
+
+    match finishing_audio_graph(options, duration, program_has_audio, normalize_filter.as_deref()) {
+        Some(audio_graph) => {
+            graph.push(audio_graph);
+            builder = builder.map(utils::audio_mix::MIX_OUTPUT_LABEL);
+        }
+        None => builder = builder.map("0:a?"),
+    }
+    if normalize_filter.is_some() {
+        builder = builder.audio_sample_rate(48000);
+    }
+    if !graph.is_empty() {
+        builder = builder.filter_complex(&graph.join(";"));
+    }
+
+    builder
+        .with_progress()
+        .output(output_path)
+        .run_with_progress(app_handle, Some(duration), progress_offset, progress_range)
+        .await
+        .map_err(|e| format!("Failed to finish export: {}", e))
+}
+
+// Builder with the program as input 0, then each audio track clip, then the watermark
+fn finishing_builder(program_path: &str, options: &ExportOptions) -> utils::ffmpeg::FfmpegBuilder {
+    let builder = options.audio_tracks.iter().fold(
+        utils::ffmpeg::FfmpegBuilder::new().input(program_path),
+        |builder, track| builder.add_input(&track.path),
+    );
+
+    match &options.watermark {
+        Some(watermark) => builder.add_input(&watermark.path),
+        None => builder,
+    }
+}
+
+// Video graph producing [vout]: text layers, then the watermark on top
+fn finishing_video_graph(options: &ExportOptions, width: u32) -> Option<String> {
+    let text_filters: Vec<String> = options.text_overlays.iter().map(|overlay| overlay.drawtext_filter()).collect();
+    if text_filters.is_empty() && options.watermark.is_none() {
+        return None;
+    }
+
+    let mut chains = Vec::new();
+    let mut current = "[0:v]";
+
+    if !text_filters.is_empty() {
+        let out = if options.watermark.is_some() { "[vtext]" } else { "[vout]" };
+        chains.push(format!("{}{}{}", current, text_filters.join(","), out));
+        current = out;
+    }
+
+    if let Some(watermark) = &options.watermark {
+        let input_index = 1 + options.audio_tracks.len();
+        chains.push(watermark.overlay_graph(input_index, current, "[vout]", width));
+    }
+
+    Some(chains.join(";"))
+}
+
+// Audio graph producing [aout]: track mix and/or a tail filter on the program audio
+fn finishing_audio_graph(
+    options: &ExportOptions,
+    duration: f64,
+    program_has_audio: bool,
+    tail_filter: Option<&str>,
+) -> Option<String> {
+    if !options.audio_tracks.is_empty() {
+        Some(utils::audio_mix::build_mix_graph(
+            &options.audio_tracks, options.ducking.as_ref(), duration, program_has_audio, tail_filter,
+        ))
+    } else {
+        match tail_filter {
+            Some(tail) if program_has_audio => Some(format!("[0:a]{}{}", tail, utils::audio_mix::MIX_OUTPUT_LABEL)),
+            _ => None,
+        }
+    }
+}
+
+// Check whether a file has at least one audio stream
+fn probe_has_audio(file_path: &str, app_handle: &tauri::AppHandle) -> bool {
+    utils::ffmpeg::execute_ffprobe(
+        app_handle,
+        &[
+            "-v", "error",
+            "-select_streams", "a",
+            "-show_entries", "stream=index",
+            "-of", "csv=p=0",
+            file_path
+        ]
+    )
+    .map(|output| output.status.success() && !String::from_utf8_lossy(&output.stdout).trim().is_empty())
+    .unwrap_or(false)
+}
+
+// Helper function running the loudnorm measurement pass over a file or range
+fn measure_loudness(
+    file_path: &str,
+    range: Option<(f64, f64)>,
+    target: &utils::loudness::LoudnessTarget,
+    app_handle: &tauri::AppHandle,
+) -> Result<utils::loudness::LoudnessMeasurement, String> {
+    let mut builder = utils::ffmpeg::FfmpegBuilder::new()
+        .input(file_path);
+
+    if let Some((start, duration)) = range {
+        builder = builder.trim(start, duration);
+    }
+
+    run_loudness_measurement(
+        builder.audio_only().loudnorm(&target.measure_filter()),
+        app_handle,
+    )
+}
+
+// Run a prepared loudnorm measurement command and parse its report
+fn run_loudness_measurement(
+    builder: utils::ffmpeg::FfmpegBuilder,
+    app_handle: &tauri::AppHandle,
+) -> Result<utils::loudness::LoudnessMeasurement, String> {
+    let stderr = builder
+        .analyze()
+        .with_app_handle(app_handle.clone())
+        .run_analysis()
+        .map_err(|e| format!("Loudness analysis failed: {}", e))?;
+
+    utils::loudness::parse_loudnorm_output(&stderr)
+        .ok_or_else(|| "Failed to parse loudness measurement (does the file have audio?)".to_string())
+}
+
+#[tauri::command]
+async fn analyze_loudness(
+    file_path: String,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    app_handle: tauri::AppHandle,
+) -> Result<utils::loudness::LoudnessMeasurement, String> {
+    if !Path::new(&file_path).exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let range = match (start_time, end_time) {
+        (Some(start), Some(end)) if start < end => Some((start, end - start)),
+        (None, None) => None,
+        _ => return Err("Start time must be less than end time".to_string()),
+    };
+
+    // Measurement is target-independent; the web preset only fills in loudnorm's required params
+    tokio::task::spawn_blocking(move || {
+        measure_loudness(&file_path, range, &utils::loudness::LoudnessTarget::web(), &app_handle)
+    })
+    .await
+    .map_err(|e| format!("Loudness analysis task failed: {}", e))?
+}
+
+// Gather the clips' captions, trimmed and retimed onto the program timeline
+fn collect_captions(clips: &[ClipExportInfo], offsets: &[f64], duration: f64) -> Vec<utils::subtitles::Cue> {
+    let mut tracks = Vec::new();
+    for (clip, offset) in clips.iter().zip(offsets) {
+        let Some(caption_path) = &clip.vtt_path else {
+            continue;
+        };
+        // Missing or malformed captions shouldn't fail an otherwise successful export
+        let cues = match utils::subtitles::read_caption_file(caption_path) {
+            Ok(cues) => cues,
+            Err(e) => {
+                eprintln!("Warning: Skipping captions for {}: {}", clip.path, e);
+                continue;
+            }
+        };
+        let timing = utils::subtitles::CueTiming {
+            trim_start: clip.trim_start,
+            trim_end: clip.trim_end,
+            speed: clip.effects.speed_factor(),
+            reverse: clip.effects.is_reversed(),
+            offset: *offset,
+        };
+        tracks.push(utils::subtitles::retime_cues(&cues, &timing));
+    }
+
+    utils::subtitles::merge_cues(tracks, duration)
+}
+
+// Write captions next to the export (same name, caption format's extension)
+fn write_caption_sidecar(cues: &[utils::subtitles::Cue], format: utils::subtitles::CaptionFormat, output_path: &str) {
+    let caption_output = Path::new(output_path).with_extension(format.extension());
+    if let Err(e) = fs::write(&caption_output, format.write(cues)) {
+        eprintln!("Warning: Failed to write captions: {}", e);
+    } else {
+        println!("Wrote {} caption cue(s) to: {:?}", cues.len(), caption_output);
+    }
+}
+
+// Remux the export with captions as a subtitle stream (streams are copied, not re-encoded)
+fn embed_captions(
+    cues: &[utils::subtitles::Cue],
+    settings: &utils::subtitles::EmbeddedCaptions,
+    output_path: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    let codec = settings.codec_for(output_path)?;
+    let output = Path::new(output_path);
+    let extension = output.extension().and_then(|e| e.to_str()).unwrap_or("mp4");
+
+    // Temp files live next to the output so the final rename stays on one filesystem
+    let captions_path = output.with_extension("embed.srt");
+    let muxed_path = output.with_extension(format!("embed.{}", extension));
+    fs::write(&captions_path, utils::subtitles::write_srt(cues))
+        .map_err(|e| format!("Failed to write temp captions: {}", e))?;
+
+    let mut builder = utils::ffmpeg::FfmpegBuilder::new()
+        .input(output_path)
+        .add_input(captions_path.to_str().ok_or("Invalid caption path")?)
+        .map("0:v")
+        .map("0:a?")
+        .map("1:0")
+        .stream_copy()
+        .subtitle_codec(codec);
+    for entry in settings.metadata() {
+        builder = builder.stream_metadata("s:s:0", &entry);
     }
 
-    // Multi-clip: use concat demuxer with trims
-    export_multi_clips(&clips, &output_path, width, height, &app_handle).await
+    let result = builder
+        .output(muxed_path.to_str().ok_or("Invalid temp path")?)
+        .run(app_handle);
+    let _ = fs::remove_file(&captions_path);
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&muxed_path);
+        return Err(format!("Failed to embed captions: {}", e));
+    }
+    fs::rename(&muxed_path, output)
+        .map_err(|e| format!("Failed to replace output with captioned version: {}", e))
 }
 
+// Helper function for composition export (picture-in-picture, stacks, grids)
+async fn export_layout(
+    clips: &[ClipExportInfo],
+    layout_graph: &utils::composition::LayoutGraph,
+    output_path: &str,
+    app_handle: &tauri::AppHandle,
+    progress_offset: u32,
+    progress_range: u32,
+) -> Result<String, String> {
+    // Each input seeks to its own trim range
+    let mut builder = utils::ffmpeg::FfmpegBuilder::new()
+        .input(&clips[0].path)
+        .trim(clips[0].trim_start, clips[0].trim_end - clips[0].trim_start);
+    for clip in &clips[1..] {
+        let start = clip.trim_start.to_string();
+        let duration = (clip.trim_end - clip.trim_start).to_string();
+        builder = builder.add_input_with_options(&["-ss", start.as_str(), "-t", duration.as_str()], &clip.path);
+    }
+
+    builder = builder
+        .filter_complex(&layout_graph.graph)
+        .map(utils::composition::LAYOUT_VIDEO_LABEL);
+    if let Some(audio) = &layout_graph.audio_label {
+        builder = builder.map(audio);
+    }
+
+    builder
+        .encode()
+        .pixel_format("yuv420p")
+        .with_progress()
+        .output(output_path)
+        .run_with_progress(app_handle, Some(layout_graph.duration), progress_offset, progress_range)
+        .await
+        .map_err(|e| format!("Failed to render layout: {}", e))
+}
 
 // Helper function for single clip export
+#[allow(clippy::too_many_arguments)]
 async fn export_single_clip(
     clip: &ClipExportInfo,
+    caption_style: Option<&utils::subtitles::CaptionStyle>,
     output_path: &str,
     width: u32,
     height: u32,
     app_handle: &tauri::AppHandle,
+    progress_offset: u32,
+    progress_range: u32,
 ) -> Result<String, String> {
     // Calculate total duration for progress calculation
-    let duration = clip.trim_end - clip.trim_start;
+    let duration = clip.output_duration();
 
     // Build FFmpeg command with volume/mute settings
-    let mut builder = utils::ffmpeg::FfmpegBuilder::new()
-        .input(&clip.path)
-        .trim(clip.trim_start, duration)
+    let builder = clip_source_builder(clip, width, height)
         .scale_with_pad(width, height)
         .encode()
         .with_progress();
 
-    // Apply audio settings if present
-    if clip.muted == Some(true) {
-        builder = builder.mute();
-    } else if let Some(vol) = clip.volume {
+    let result = apply_clip_effects(builder, clip, caption_style)
+        .output(output_path)
+        .run_with_progress(app_handle, Some(duration), progress_offset, progress_range)
+        .await;
+
+    match result {
+        Ok(_) => Ok(output_path.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// Base builder for a clip: trimmed source media, or generated video (title card or gap) with silent audio
+fn clip_source_builder(clip: &ClipExportInfo, width: u32, height: u32) -> utils::ffmpeg::FfmpegBuilder {
+    let duration = clip.trim_end - clip.trim_start;
+    let generated = |source: &str| {
+        utils::ffmpeg::FfmpegBuilder::new()
+            .input_format("lavfi")
+            .input(source)
+            .trim(0.0, duration)
+            .add_input_with_options(&["-f", "lavfi"], utils::text_overlay::SILENT_AUDIO_SOURCE)
+            .shortest()
+    };
+
+    match &clip.title_card {
+        Some(card) => generated(&card.color_source(width, height, duration)).text_overlay(card.text_overlay()),
+        None if clip.gap => generated(&utils::text_overlay::color_source("black", width, height, duration)),
+        None => utils::ffmpeg::FfmpegBuilder::new()
+            .input(&clip.path)
+            .trim(clip.trim_start, duration),
+    }
+}
+
+// Apply a clip's video (captions/fades/grading) and audio (volume/gain/fades/mute) settings to a builder
+fn apply_clip_effects(
+    mut builder: utils::ffmpeg::FfmpegBuilder,
+    clip: &ClipExportInfo,
+    caption_style: Option<&utils::subtitles::CaptionStyle>,
+) -> utils::ffmpeg::FfmpegBuilder {
+    if let (Some(style), Some(captions)) = (caption_style, &clip.vtt_path) {
+        builder = builder.burn_subtitles(&style.subtitles_filter(captions));
+    }
+    if clip.effects.is_reversed() {
+        builder = builder.reverse();
+    }
+    if let Some(speed) = clip.effects.speed {
+        builder = builder.speed(speed);
+    }
+    if let Some(lut) = &clip.effects.lut_path {
+        builder = builder.lut3d(lut);
+    }
+    if let Some(color) = &clip.effects.color {
+        builder = builder.color_adjust(color.clone());
+    }
+    if let Some(fade_in) = clip.effects.video_fade_in {
+        builder = builder.video_fade_in(fade_in);
+    }
+    if let Some(fade_out) = clip.effects.video_fade_out {
+        builder = builder.video_fade_out(fade_out);
+    }
+
+    if clip.effects.is_muted() {
+        return builder.mute();
+    }
+
+    if let Some(vol) = clip.effects.volume {
         builder = builder.volume(vol);
     }
+    if let Some(db) = clip.effects.gain_db {
+        builder = builder.gain_db(db);
+    }
+    if let Some(fade_in) = clip.effects.audio_fade_in {
+        builder = builder.audio_fade_in(fade_in);
+    }
+    if let Some(fade_out) = clip.effects.audio_fade_out {
+        builder = builder.audio_fade_out(fade_out);
+    }
+
+    builder
+}
+
+// Join pre-rendered clips with xfade/acrossfade transitions in a single encode
+async fn render_transitions(
+    clip_paths: &[PathBuf],
+    clips: &[ClipExportInfo],
+    transitions: &[utils::transitions::Transition],
+    output_path: &str,
+    app_handle: &tauri::AppHandle,
+    progress_offset: u32,
+    progress_range: u32,
+) -> Result<String, String> {
+    let durations: Vec<f64> = clips.iter().map(|c| c.output_duration()).collect();
+    // Screen recordings often have no audio track; those inputs get silence
+    let has_audio = clip_paths
+        .iter()
+        .map(|path| Ok(probe_has_audio(path.to_str().ok_or("Invalid temp path")?, app_handle)))
+        .collect::<Result<Vec<bool>, String>>()?;
+    let graph = utils::transitions::build_transition_graph(&durations, &has_audio, transitions);
+
+    let mut builder = utils::ffmpeg::FfmpegBuilder::new()
+        .input(clip_paths[0].to_str().ok_or("Invalid temp path")?);
+    for path in &clip_paths[1..] {
+        builder = builder.add_input(path.to_str().ok_or("Invalid temp path")?);
+    }
+
+    builder
+        .filter_complex(&graph)
+        .map("[vout]")
+        .map("[aout]")
+        .encode()
+        .pixel_format("yuv420p")
+        .with_progress()
+        .output(output_path)
+        .run_with_progress(
+            app_handle,
+            Some(utils::transitions::timeline_duration(&durations, transitions)),
+            progress_offset,
+            progress_range,
+        )
+        .await
+        .map_err(|e| format!("Failed to render transitions: {}", e))
+}
+
+// Helper function for multi-track export: each track is rendered as its own sequence,
+// then higher tracks are overlaid on lower ones while they have clips and the audio is mixed
+#[allow(clippy::too_many_arguments)]
+async fn export_tracks(
+    layers: &[&[ClipExportInfo]],
+    spans: &[Vec<(f64, f64)>],
+    caption_style: Option<&utils::subtitles::CaptionStyle>,
+    output_path: &str,
+    temp_dir: &Path,
+    width: u32,
+    height: u32,
+    duration: f64,
+    app_handle: &tauri::AppHandle,
+    progress_offset: u32,
+    progress_range: u32,
+) -> Result<String, String> {
+    // Track renders take 80% of this phase, compositing the rest
+    let track_range = progress_range * 4 / 5 / layers.len() as u32;
+    let mut track_paths = Vec::new();
+    for (i, layer) in layers.iter().enumerate() {
+        let track_path = temp_dir.join(format!("track_{}.mp4", i));
+        let track_output = track_path.to_str().ok_or("Invalid temp path")?;
+        let track_offset = progress_offset + track_range * i as u32;
+        if layer.len() == 1 {
+            export_single_clip(&layer[0], caption_style, track_output, width, height, app_handle, track_offset, track_range).await
+        } else {
+            export_multi_clips(layer, &[], caption_style, track_output, temp_dir, width, height, app_handle, track_offset, track_range).await
+        }
+        .map_err(|e| format!("Failed to render track {}: {}", i, e))?;
+        track_paths.push(track_output.to_string());
+    }
+
+    // Screen recordings often have no audio track; those tracks are left out of the mix
+    let (probe_paths, handle) = (track_paths.clone(), app_handle.clone());
+    let has_audio = tokio::task::spawn_blocking(move || {
+        probe_paths.iter().map(|path| probe_has_audio(path, &handle)).collect::<Vec<bool>>()
+    })
+    .await
+    .map_err(|e| format!("Audio probe task failed: {}", e))?;
+    let track_layers: Vec<utils::timeline::TrackLayer> = spans.iter()
+        .zip(has_audio)
+        .map(|(spans, has_audio)| utils::timeline::TrackLayer { spans: spans.clone(), has_audio })
+        .collect();
+    let (graph, mixed_audio) = utils::timeline::build_layer_graph(&track_layers);
+
+    let mut builder = utils::ffmpeg::FfmpegBuilder::new().input(&track_paths[0]);
+    for path in &track_paths[1..] {
+        builder = builder.add_input(path);
+    }
+    builder = builder.filter_complex(&graph).map("[vout]");
+    if mixed_audio {
+        builder = builder.map("[aout]");
+    }
 
+    let composite_offset = progress_offset + track_range * layers.len() as u32;
     let result = builder
+        .encode()
+        .pixel_format("yuv420p")
+        .with_progress()
         .output(output_path)
-        .run_with_progress(app_handle, Some(duration), 0, 100) // Single clip: 0-100%
-        .await;
+        .run_with_progress(app_handle, Some(duration), composite_offset, progress_offset + progress_range - composite_offset)
+        .await
+        .map_err(|e| format!("Failed to composite tracks: {}", e));
 
-    match result {
-        Ok(_) => {
-            // Copy VTT caption file if it exists
-            if let Some(vtt_path) = &clip.vtt_path {
-                let vtt_source = Path::new(vtt_path);
-                if vtt_source.exists() {
-                    // Create destination VTT path (same name as output, but .vtt extension)
-                    let output_vtt = Path::new(output_path).with_extension("vtt");
-                    if let Err(e) = fs::copy(vtt_source, &output_vtt) {
-                        eprintln!("Warning: Failed to copy VTT file: {}", e);
-                    } else {
-                        println!("Copied VTT captions to: {:?}", output_vtt);
-                    }
-                }
-            }
-            Ok(output_path.to_string())
-        },
-        Err(e) => Err(e.to_string()),
+    for path in &track_paths {
+        let _ = fs::remove_file(path);
     }
+    result
 }
 
-// Helper function for multi-clip export using concat demuxer
+// Helper function for multi-clip export using concat demuxer, or an xfade graph when transitions are set
+#[allow(clippy::too_many_arguments)]
 async fn export_multi_clips(
     clips: &[ClipExportInfo],
+    transitions: &[utils::transitions::Transition],
+    caption_style: Option<&utils::subtitles::CaptionStyle>,
     output_path: &str,
+    temp_dir: &Path,
     width: u32,
     height: u32,
     app_handle: &tauri::AppHandle,
+    progress_offset: u32,
+    progress_range: u32,
 ) -> Result<String, String> {
-    // Get app data directory for temp files
-    let app_data_dir = app_handle.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-
     // For multi-clip with trims, we need to pre-process each clip first
     // Then concatenate the trimmed versions
     let mut trimmed_clip_paths = Vec::new();
 
     // Get total estimated duration for individual clips (for accurate progress)
-    let total_clip_duration: f64 = clips.iter().map(|c| c.trim_end - c.trim_start).sum();
+    let total_clip_duration: f64 = clips.iter().map(|c| c.output_duration()).sum();
+
+    // Clip processing takes 90% of this phase, concat the remaining 10%.
+    // Transitions re-encode the whole program, so that pass gets a larger share.
+    let clips_share = if transitions.is_empty() { 0.9 } else { 0.6 };
+    let clips_range = progress_range as f64 * clips_share;
+    let concat_offset = progress_offset + clips_range as u32;
+    let concat_range = progress_range - clips_range as u32;
 
     // Emit initial progress
-    let _ = app_handle.emit("ffmpeg-progress", 0u32);
+    let _ = app_handle.emit("ffmpeg-progress", progress_offset);
 
     let mut completed_clip_duration = 0.0f64;
 
     for (i, clip) in clips.iter().enumerate() {
-        let temp_output = app_data_dir.join(format!("temp_clip_{}.mp4", i));
-        let duration = clip.trim_end - clip.trim_start;
+        let temp_output = temp_dir.join(format!("clip_{}.mp4", i));
+        let duration = clip.output_duration();
 
         // Calculate progress offset and range for this clip
-        let progress_offset = (completed_clip_duration / total_clip_duration * 90.0) as u32;
-        let progress_range = ((duration / total_clip_duration) * 90.0) as u32;
+        let clip_offset = progress_offset + (completed_clip_duration / total_clip_duration * clips_range) as u32;
+        let clip_range = ((duration / total_clip_duration) * clips_range) as u32;
 
         // Build FFmpeg command with volume/mute settings
-        let mut builder = utils::ffmpeg::FfmpegBuilder::new()
-            .input(&clip.path)
-            .trim(clip.trim_start, duration)
+        let builder = clip_source_builder(clip, width, height)
             .scale_with_pad(width, height)
             .encode()
             .with_progress();
 
-        // Apply audio settings if present
-        if clip.muted == Some(true) {
-            builder = builder.mute();
-        } else if let Some(vol) = clip.volume {
-            builder = builder.volume(vol);
-        }
-
-        let result = builder
+        let result = apply_clip_effects(builder, clip, caption_style)
             .output(temp_output.to_str().ok_or("Invalid temp path")?)
-            .run_with_progress(app_handle, Some(duration), progress_offset, progress_range)
+            .run_with_progress(app_handle, Some(duration), clip_offset, clip_range)
             .await;
 
         if let Err(e) = result {
@@ -681,8 +1825,19 @@ async fn export_multi_clips(
         completed_clip_duration += duration;
     }
 
+    if !transitions.is_empty() {
+        let result = render_transitions(
+            &trimmed_clip_paths, clips, transitions, output_path, app_handle, concat_offset, concat_range,
+        ).await;
+
+        for temp_path in &trimmed_clip_paths {
+            let _ = fs::remove_file(temp_path);
+        }
+        return result;
+    }
+
     // Create concat list file
-    let concat_list_path = app_data_dir.join("concat_list.txt");
+    let concat_list_path = temp_dir.join("concat_list.txt");
     let mut concat_file = fs::File::create(&concat_list_path)
         .map_err(|e| format!("Failed to create concat list file: {}", e))?;
 
@@ -697,14 +1852,14 @@ async fn export_multi_clips(
         .map_err(|e| format!("Failed to flush concat list: {}", e))?;
     drop(concat_file); // Close file
 
-    // Use builder for concat operation - progress starts from 90%
+    // Use builder for concat operation - progress starts after clip processing
     let concat_duration_estimate = total_clip_duration * 0.1; // Estimate 10% of total time for concat
     let result = utils::ffmpeg::FfmpegBuilder::new()
         .concat(concat_list_path.to_str().ok_or("Invalid concat list path")?)
         .stream_copy()
         .with_progress()
         .output(output_path)
-        .run_with_progress(app_handle, Some(concat_duration_estimate), 90, 10) // 90% offset, 10% range
+        .run_with_progress(app_handle, Some(concat_duration_estimate), concat_offset, concat_range)
         .await;
 
     // Clean up temp files
@@ -714,20 +1869,7 @@ async fn export_multi_clips(
     let _ = fs::remove_file(&concat_list_path);
 
     match result {
-        Ok(_) => {
-            // For multi-clip exports, we don't merge VTT files (would be complex)
-            // But we could copy the first clip's VTT if only one has captions
-            // For now, just log a message if captions exist
-            let clips_with_captions: Vec<_> = clips.iter()
-                .filter(|c| c.vtt_path.is_some())
-                .collect();
-
-            if !clips_with_captions.is_empty() {
-                println!("Note: {} clip(s) have captions. Multi-clip VTT merging not yet supported.", clips_with_captions.len());
-            }
-
-            Ok(output_path.to_string())
-        },
+        Ok(_) => Ok(output_path.to_string()),
         Err(e) => Err(e.to_string()),
     }
 }
@@ -846,14 +1988,29 @@ async fn save_workspace(state_json: String, app_handle: tauri::AppHandle) -> Res
 
     let workspace_path = app_data_dir.join("workspace.json");
 
-    fs::write(&workspace_path, state_json)
+    // Parse into the timeline model so malformed state never reaches disk
+    let state = utils::workspace::parse_current(&state_json)?;
+    let json = serde_json::to_string(&state)
+        .map_err(|e| format!("Failed to serialize workspace: {}", e))?;
+
+    fs::write(&workspace_path, json)
         .map_err(|e| format!("Failed to save workspace: {}", e))?;
 
     Ok(())
 }
 
+// Name to keep an old workspace.json under, e.g. workspace.unreadable-<secs>.json
+fn workspace_backup_path(workspace_path: &Path, label: &str) -> PathBuf {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    workspace_path.with_file_name(format!("workspace.{}-{}.json", label, secs))
+}
+
+// Returns None when there is no saved workspace yet
 #[tauri::command]
-async fn load_workspace(app_handle: tauri::AppHandle) -> Result<String, String> {
+async fn load_workspace(app_handle: tauri::AppHandle) -> Result<Option<utils::workspace::LoadedWorkspace>, String> {
     let app_data_dir = app_handle.path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data directory: {}", e))?;
@@ -861,13 +2018,47 @@ async fn load_workspace(app_handle: tauri::AppHandle) -> Result<String, String>
     let workspace_path = app_data_dir.join("workspace.json");
 
     if !workspace_path.exists() {
-        return Err("No saved workspace found".to_string());
+        return Ok(None);
     }
 
     let content = fs::read_to_string(&workspace_path)
         .map_err(|e| format!("Failed to load workspace: {}", e))?;
+    let loaded = match utils::workspace::load(&content) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            // Move the broken file aside so saves start a fresh workspace without losing it.
+            // If it can't be moved, fail so the frontend doesn't save over it.
+            let backup = workspace_backup_path(&workspace_path, "unreadable");
+            fs::rename(&workspace_path, &backup)
+                .map_err(|rename_error| format!("{} (and it couldn't be moved aside: {})", e, rename_error))?;
+            eprintln!("Warning: Unreadable workspace moved to {:?}: {}", backup, e);
+            return Ok(Some(utils::workspace::LoadedWorkspace::replacing_unreadable(
+                format!("{} (the file was moved to {:?})", e, backup),
+            )));
+        }
+    };
 
-    Ok(content)
+    // The next save drops whatever was skipped, so keep the original
+    if !loaded.warnings.is_empty() {
+        let backup = workspace_backup_path(&workspace_path, "recovered");
+        fs::copy(&workspace_path, &backup)
+            .map_err(|e| format!("Failed to back up workspace: {}", e))?;
+        eprintln!("Warning: Loaded workspace with problems (original kept at {:?}): {:?}", backup, loaded.warnings);
+    }
+
+    // Keep the old file next to the upgraded one in case the user downgrades
+    if loaded.was_migrated() {
+        let backup_path = app_data_dir.join(format!("workspace.v{}.json", loaded.stored_version));
+        let json = serde_json::to_string(&loaded.workspace)
+            .map_err(|e| format!("Failed to serialize workspace: {}", e))?;
+        if let Err(e) = fs::copy(&workspace_path, &backup_path).and_then(|_| fs::write(&workspace_path, json)) {
+            eprintln!("Warning: Failed to rewrite upgraded workspace: {}", e);
+        } else {
+            println!("Upgraded workspace from version {} (backup: {:?})", loaded.stored_version, backup_path);
+        }
+    }
+
+    Ok(Some(loaded))
 }
 
 #[tauri::command]
@@ -960,6 +2151,290 @@ async fn reset_workspace(app_handle: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+// Resolve an optional start/end into an analysis range (start, duration)
+fn analysis_range(
+    file_path: &str,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    app_handle: &tauri::AppHandle,
+) -> Result<(f64, f64), String> {
+    let start = start_time.unwrap_or(0.0).max(0.0);
+    let end = match end_time {
+        Some(end) => end,
+        None => probe_duration(file_path, app_handle)?,
+    };
+    if start >= end {
+        return Err("Start time must be less than end time".to_string());
+    }
+    Ok((start, end - start))
+}
+
+#[tauri::command]
+async fn detect_silence(
+    file_path: String,
+    settings: Option<utils::detection::SilenceSettings>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    padding: Option<f64>,
+    app_handle: tauri::AppHandle,
+) -> Result<SilenceReport, String> {
+    if !Path::new(&file_path).exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+    let settings = settings.unwrap_or_default();
+    let filter = settings.filter();
+    let source = file_path.clone();
+    let (start, duration, stderr) = tokio::task::spawn_blocking(move || {
+        let (start, duration) = analysis_range(&source, start_time, end_time, &app_handle)?;
+        let stderr = utils::ffmpeg::FfmpegBuilder::new()
+            .input(&source)
+            .trim(start, duration)
+            .audio_only()
+            .audio_filter(&filter)
+            .analyze()
+            .with_app_handle(app_handle)
+            .run_analysis()
+            .map_err(|e| format!("Silence detection failed: {}", e))?;
+        Ok::<_, String>((start, duration, stderr))
+    })
+    .await
+    .map_err(|e| format!("Silence detection task failed: {}", e))??;
+
+    // Detected times are relative to the seek point
+    let silences: Vec<utils::detection::Interval> = utils::detection::parse_silencedetect(&stderr, Some(duration))
+        .into_iter()
+        .map(|s| utils::detection::Interval { start: s.start + start, end: s.end + start })
+        .collect();
+
+    // Keeps shorter than a few frames aren't worth a cut
+    let keep_segments = utils::detection::keep_segments(&silences, start, start + duration, padding.unwrap_or(0.15), 0.1)
+        .into_iter()
+        .map(|k| TrimSegment { path: file_path.clone(), trim_start: k.start, trim_end: k.end })
+        .collect();
+
+    Ok(SilenceReport { silences, keep_segments })
+}
+
+// Scene cuts in source time between start and start + duration
+async fn scene_cuts(
+    file_path: &str,
+    settings: &utils::detection::SceneSettings,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    app_handle: tauri::AppHandle,
+) -> Result<(Vec<utils::detection::SceneCut>, f64, f64), String> {
+    if !Path::new(file_path).exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+    let filter = settings.filter();
+    let source = file_path.to_string();
+    let (start, duration, stderr) = tokio::task::spawn_blocking(move || {
+        let (start, duration) = analysis_range(&source, start_time, end_time, &app_handle)?;
+        let stderr = utils::ffmpeg::FfmpegBuilder::new()
+            .input(&source)
+            .trim(start, duration)
+            .video_only()
+            .video_filter(&filter)
+            .analyze()
+            .with_app_handle(app_handle)
+            .run_analysis()
+            .map_err(|e| format!("Scene detection failed: {}", e))?;
+        Ok::<_, String>((start, duration, stderr))
+    })
+    .await
+    .map_err(|e| format!("Scene detection task failed: {}", e))??;
+
+    // Detected times are relative to the seek point
+    let cuts: Vec<utils::detection::SceneCut> = utils::detection::parse_scene_cuts(&stderr)
+        .into_iter()
+        .map(|cut| utils::detection::SceneCut { time: cut.time + start, score: cut.score })
+        .collect();
+    let cuts = utils::detection::filter_scene_cuts(&cuts, start, start + duration, settings.min_scene_length);
+    Ok((cuts, start, duration))
+}
+
+#[tauri::command]
+async fn detect_scenes(
+    file_path: String,
+    settings: Option<utils::detection::SceneSettings>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    app_handle: tauri::AppHandle,
+) -> Result<SceneReport, String> {
+    let settings = settings.unwrap_or_default();
+    let (cuts, start, duration) = scene_cuts(&file_path, &settings, start_time, end_time, app_handle).await?;
+
+    let times: Vec<f64> = cuts.iter().map(|c| c.time).collect();
+    let segments = utils::detection::split_at(&times, start, start + duration)
+        .into_iter()
+        .map(|s| TrimSegment { path: file_path.clone(), trim_start: s.start, trim_end: s.end })
+        .collect();
+
+    Ok(SceneReport { cuts, segments })
+}
+
+// Virtual split of a clip at its detected scene cuts; the sub-clips keep referencing the source
+#[tauri::command]
+async fn split_clip_at_scenes(
+    clip: utils::clip::Clip,
+    settings: Option<utils::detection::SceneSettings>,
+    id_prefix: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<utils::clip::Clip>, String> {
+    if clip.title_card.is_some() {
+        return Err("Title cards have no scenes to detect".to_string());
+    }
+    let settings = settings.unwrap_or_default();
+    let (cuts, _, _) = scene_cuts(&clip.path, &settings, Some(clip.trim_start), Some(clip.trim_end), app_handle).await?;
+
+    let times: Vec<f64> = cuts.iter().map(|c| c.time).collect();
+    clip.split_into(&utils::detection::split_at(&times, clip.trim_start, clip.trim_end), &id_prefix)
+}
+
+// QC pass over a clip or an exported file; intervals are in source time
+#[tauri::command]
+async fn check_video_quality(
+    file_path: String,
+    settings: Option<utils::detection::QualityCheckSettings>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    app_handle: tauri::AppHandle,
+) -> Result<utils::detection::QualityReport, String> {
+    if !Path::new(&file_path).exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+    let settings = settings.unwrap_or_default();
+    let filter = settings.filter();
+    let source = file_path.clone();
+    let (start, duration, stderr) = tokio::task::spawn_blocking(move || {
+        let (start, duration) = analysis_range(&source, start_time, end_time, &app_handle)?;
+        let stderr = utils::ffmpeg::FfmpegBuilder::new()
+            .input(&source)
+            .trim(start, duration)
+            .video_only()
+            .video_filter(&filter)
+            .analyze()
+            .with_app_handle(app_handle)
+            .run_analysis()
+            .map_err(|e| format!("Quality check failed: {}", e))?;
+        Ok::<_, String>((start, duration, stderr))
+    })
+    .await
+    .map_err(|e| format!("Quality check task failed: {}", e))??;
+
+    Ok(utils::detection::parse_quality_report(&stderr, Some(duration)).offset(start))
+}
+
+#[tauri::command]
+async fn read_captions(file_path: String) -> Result<Vec<utils::subtitles::Cue>, String> {
+    utils::subtitles::read_caption_file(&file_path)
+}
+
+#[tauri::command]
+async fn convert_captions(input_path: String, output_path: String) -> Result<String, String> {
+    let cues = utils::subtitles::read_caption_file(&input_path)?;
+    utils::subtitles::write_edited_captions(&input_path, &output_path, &cues)?;
+    Ok(output_path)
+}
+
+#[tauri::command]
+async fn shift_captions(file_path: String, output_path: String, offset: f64) -> Result<Vec<utils::subtitles::Cue>, String> {
+    let cues = utils::subtitles::read_caption_file(&file_path)?;
+    let shifted = utils::subtitles::shift_cues(&cues, offset);
+    utils::subtitles::write_edited_captions(&file_path, &output_path, &shifted)?;
+    Ok(shifted)
+}
+
+#[tauri::command]
+async fn split_caption(file_path: String, output_path: String, index: usize, at: f64) -> Result<Vec<utils::subtitles::Cue>, String> {
+    let cues = utils::subtitles::read_caption_file(&file_path)?;
+    let split = utils::subtitles::split_cue(&cues, index, at)?;
+    utils::subtitles::write_edited_captions(&file_path, &output_path, &split)?;
+    Ok(split)
+}
+
+#[tauri::command]
+async fn merge_captions(file_path: String, output_path: String, index: usize) -> Result<Vec<utils::subtitles::Cue>, String> {
+    let cues = utils::subtitles::read_caption_file(&file_path)?;
+    let merged = utils::subtitles::merge_with_next(&cues, index)?;
+    utils::subtitles::write_edited_captions(&file_path, &output_path, &merged)?;
+    Ok(merged)
+}
+
+#[tauri::command]
+async fn rewrap_captions(file_path: String, output_path: String, max_chars: usize) -> Result<Vec<utils::subtitles::Cue>, String> {
+    let cues = utils::subtitles::read_caption_file(&file_path)?;
+    let wrapped = utils::subtitles::rewrap_cues(&cues, max_chars)?;
+    utils::subtitles::write_edited_captions(&file_path, &output_path, &wrapped)?;
+    Ok(wrapped)
+}
+
+#[tauri::command]
+async fn transcribe_media(
+    video_path: String,
+    settings: utils::transcription::TranscriptionSettings,
+    language: Option<String>,
+    word_timestamps: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<TranscriptionOutput, String> {
+    let input_path = Path::new(&video_path);
+    if !input_path.exists() {
+        return Err(format!("Video file not found: {}", video_path));
+    }
+    let backend = settings.backend()?;
+
+    // Extract 16 kHz mono audio in the format the backend reads
+    let audio_dir = app_handle.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?
+        .join("audio");
+    fs::create_dir_all(&audio_dir)
+        .map_err(|e| format!("Failed to create audio directory: {}", e))?;
+    let file_name = input_path.file_stem()
+        .ok_or("Invalid file path: no filename")?
+        .to_str()
+        .ok_or("Invalid filename encoding")?;
+    let (extension, codec) = backend.audio_format();
+    let audio_path = utils::audio_export::reserve_output_path(&audio_dir, &format!("{}.transcribe", file_name), extension)?;
+
+    // Extraction and transcription can take minutes on CPU, keep both off the async runtime
+    println!("Transcribing {} with {}", video_path, backend.name());
+    let task_video_path = video_path.clone();
+    let task_audio_path = audio_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        utils::ffmpeg::FfmpegBuilder::new()
+            .input(&task_video_path)
+            .audio_only()
+            .audio_sample_rate(16000)
+            .audio_channels(1)
+            .audio_codec(codec)
+            .output(task_audio_path.to_str().ok_or("Invalid audio path")?)
+            .run(&app_handle)
+            .map_err(|e| format!("Failed to extract audio: {}", e))?;
+        backend.transcribe(&task_audio_path, language.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Transcription task failed: {}", e));
+    let _ = fs::remove_file(&audio_path);
+    let transcript = result??;
+
+    // Captions are saved next to the video (same name, .vtt extension), never over existing captions
+    let vtt_path = utils::audio_export::unique_output_path(
+        input_path.parent().ok_or("Invalid file path: no parent directory")?,
+        file_name,
+        "vtt",
+    );
+    fs::write(&vtt_path, transcript.to_vtt(word_timestamps.unwrap_or(false)))
+        .map_err(|e| format!("Failed to write captions: {}", e))?;
+
+    Ok(TranscriptionOutput {
+        text: transcript.text(),
+        vtt_path: vtt_path.to_str().ok_or("Invalid caption path")?.to_string(),
+        language: transcript.language,
+        segments: transcript.segments,
+    })
+}
+
 #[tauri::command]
 async fn write_text_file(
     file_path: String,
@@ -970,17 +2445,58 @@ async fn write_text_file(
     Ok(())
 }
 
+// Audio-only output settings shared by extract_audio and export_audio
+fn apply_audio_options(
+    mut builder: utils::ffmpeg::FfmpegBuilder,
+    options: &utils::audio_export::AudioExtractOptions,
+) -> utils::ffmpeg::FfmpegBuilder {
+    builder = builder.audio_only().audio_codec(options.format.codec());
+    if let Some(rate) = options.sample_rate {
+        builder = builder.audio_sample_rate(rate);
+    }
+    if let Some(channels) = options.channels {
+        builder = builder.audio_channels(channels);
+    }
+    if let Some(bitrate) = options.bitrate.as_ref().filter(|_| !options.format.is_lossless()) {
+        builder = builder.audio_bitrate(bitrate);
+    }
+    builder
+}
+
+// Probe a media file's duration in seconds
+fn probe_duration(file_path: &str, app_handle: &tauri::AppHandle) -> Result<f64, String> {
+    let output = utils::ffmpeg::execute_ffprobe(
+        app_handle,
+        &[
+            "-v", "error",
+            "-show_entries", "format=duration",
+            "-of", "csv=p=0",
+            file_path
+        ]
+    ).map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("Failed to read duration of {}", file_path))
+}
+
 #[tauri::command]
 async fn extract_audio(
     video_path: String,
+    options: Option<utils::audio_export::AudioExtractOptions>,
     app_handle: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<AudioExtractResult, String> {
     // Validate input file exists
     let input_path = Path::new(&video_path);
     if !input_path.exists() {
         return Err(format!("Video file not found: {}", video_path));
     }
 
+    // Default to 16 kHz mono MP3 for Whisper
+    let options = options.unwrap_or_else(utils::audio_export::AudioExtractOptions::transcription);
+    options.validate()?;
+
     // Get app data directory and create audio subdirectory
     let app_data_dir = app_handle.path()
         .app_data_dir()
@@ -989,56 +2505,128 @@ async fn extract_audio(
     fs::create_dir_all(&audio_dir)
         .map_err(|e| format!("Failed to create audio directory: {}", e))?;
 
-    // Generate output filename (mp3 format for better compatibility with Whisper)
+    // Generate an output filename that doesn't overwrite earlier extractions
     let file_name = input_path.file_stem()
         .ok_or("Invalid file path: no filename")?
         .to_str()
         .ok_or("Invalid filename encoding")?;
-    let audio_filename = format!("{}.mp3", file_name);
-    let audio_path = audio_dir.join(&audio_filename);
-
-    // Extract audio using FFmpeg
-    // Using -vn (no video), -ar 16000 (16kHz for Whisper), -ac 1 (mono), -b:a 128k (128kbps)
-    let args = vec![
-        "-i".to_string(), video_path.clone(),
-        "-vn".to_string(),
-        "-ar".to_string(), "16000".to_string(),
-        "-ac".to_string(), "1".to_string(),
-        "-b:a".to_string(), "128k".to_string(),
-        "-y".to_string(),
-        audio_path.to_str().ok_or("Invalid audio path")?.to_string(),
-    ];
+    let audio_path = utils::audio_export::unique_output_path(&audio_dir, file_name, options.format.extension());
+    let audio_path = audio_path.to_str().ok_or("Invalid audio path")?.to_string();
+
+    let mut builder = utils::ffmpeg::FfmpegBuilder::new().input(&video_path);
+    match options.range() {
+        Some((start, Some(duration))) => builder = builder.trim(start, duration),
+        Some((start, None)) => builder = builder.seek(start),
+        None => {}
+    }
 
-    // Use FFmpeg builder's execute_command approach
-    let binary_path = match utils::ffmpeg::FfmpegBuilder::resolve_sidecar_binary(&app_handle, "ffmpeg") {
-        Ok(path) => path,
-        Err(e) => {
-            eprintln!("Warning: {}", e);
-            std::path::PathBuf::from("ffmpeg")
-        }
-    };
+    apply_audio_options(builder, &options)
+        .output(&audio_path)
+        .run(&app_handle)
+        .map_err(|e| format!("FFmpeg failed to extract audio: {}", e))?;
 
-    let output = tokio::process::Command::new(&binary_path)
-        .args(&args)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+    // Verify output file was created
+    if !Path::new(&audio_path).exists() {
+        return Err("Audio file was not created".to_string());
+    }
 
-    if !output.status.success() {
-        return Err(format!(
-            "FFmpeg failed to extract audio: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
+    let duration = probe_duration(&audio_path, &app_handle)?;
+    Ok(AudioExtractResult { path: audio_path, duration })
+}
+
+#[tauri::command]
+async fn export_audio(
+    clips: Vec<utils::clip::Clip>,
+    output_path: String,
+    options: Option<utils::audio_export::AudioExtractOptions>,
+    app_handle: tauri::AppHandle,
+) -> Result<AudioExtractResult, String> {
+    if clips.is_empty() {
+        return Err("No clips to export".to_string());
+    }
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    options.format.check_output_path(&output_path)?;
+    if options.range().is_some() {
+        return Err("Timeline audio exports use the clip trims; start/end are not supported".to_string());
     }
 
-    // Verify output file was created
-    if !audio_path.exists() {
-        return Err("Audio file was not created".to_string());
+    let timeline = utils::timeline::Timeline::from_clips(clips);
+    let issues = timeline_issues(&timeline, &app_handle);
+    if !issues.is_empty() {
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        return Err(format!("Timeline has problems: {}", messages.join("; ")));
+    }
+    for clip in timeline.clips() {
+        clip.effects.check_reverse(clip.trim_end - clip.trim_start)?;
+    }
+
+    // Each track plays as a sequence and the tracks are mixed. Every clip becomes one
+    // pre-trimmed input; gaps, title cards and silent clips contribute silence.
+    let total_duration = timeline.duration();
+    let mut builder = utils::ffmpeg::FfmpegBuilder::new();
+    let mut tracks = Vec::new();
+    let mut input = 0;
+    for track in &timeline.tracks {
+        let mut segments = Vec::new();
+        for item in track.sequence(total_duration) {
+            let segment = match item {
+                utils::timeline::SequenceItem::Clip(clip) => utils::audio_export::AudioSegment {
+                    duration: clip.trim_end - clip.trim_start,
+                    gain: clip.effects.linear_gain(),
+                    speed: clip.effects.speed,
+                    reverse: clip.effects.is_reversed(),
+                    fade_in: clip.effects.audio_fade_in,
+                    fade_out: clip.effects.audio_fade_out,
+                },
+                utils::timeline::SequenceItem::Gap(duration) => utils::audio_export::AudioSegment {
+                    duration,
+                    gain: 1.0,
+                    speed: None,
+                    reverse: false,
+                    fade_in: None,
+                    fade_out: None,
+                },
+            };
+            let source = match item {
+                utils::timeline::SequenceItem::Clip(clip)
+                    if clip.title_card.is_none() && probe_has_audio(&clip.path, &app_handle) => Some(clip),
+                _ => None,
+            };
+
+            let duration = segment.duration;
+            builder = match (input, source) {
+                (0, None) => builder.input_format("lavfi").input(utils::text_overlay::SILENT_AUDIO_SOURCE).trim(0.0, duration),
+                (0, Some(clip)) => builder.input(&clip.path).trim(clip.trim_start, duration),
+                (_, None) => {
+                    let t = duration.to_string();
+                    builder.add_input_with_options(&["-f", "lavfi", "-t", t.as_str()], utils::text_overlay::SILENT_AUDIO_SOURCE)
+                }
+                (_, Some(clip)) => {
+                    let (ss, t) = (clip.trim_start.to_string(), duration.to_string());
+                    builder.add_input_with_options(&["-ss", ss.as_str(), "-t", t.as_str()], &clip.path)
+                }
+            };
+            segments.push(segment);
+            input += 1;
+        }
+        tracks.push(segments);
     }
 
-    Ok(audio_path.to_str()
-        .ok_or("Invalid audio path")?
-        .to_string())
+    let graph = utils::audio_export::build_audio_tracks_graph(&tracks);
+    builder = builder
+        .filter_complex(&graph)
+        .map(utils::audio_export::AUDIO_CONCAT_LABEL);
+
+    apply_audio_options(builder, &options)
+        .with_progress()
+        .output(&output_path)
+        .run_with_progress(&app_handle, Some(total_duration), 0, 100)
+        .await
+        .map_err(|e| format!("Failed to export audio: {}", e))?;
+
+    let duration = probe_duration(&output_path, &app_handle)?;
+    Ok(AudioExtractResult { path: output_path, duration })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -1079,7 +2667,7 @@ pub fn run() {
   tauri::Builder::default()
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
-    .invoke_handler(tauri::generate_handler![check_ffmpeg, import_file, generate_thumbnail, regenerate_thumbnails, trim_clip, save_recording, export_video, record_webcam_clip, save_workspace, load_workspace, list_clips, delete_clip, reset_workspace, extract_audio, write_text_file])
+    .invoke_handler(tauri::generate_handler![check_ffmpeg, import_file, generate_thumbnail, regenerate_thumbnails, trim_clip, split_clip, save_recording, export_video, record_webcam_clip, save_workspace, load_workspace, list_clips, delete_clip, reset_workspace, extract_audio, write_text_file, analyze_loudness, read_captions, convert_captions, shift_captions, split_caption, merge_captions, rewrap_captions, transcribe_media, export_audio, detect_silence, detect_scenes, check_video_quality, validate_timeline, set_clip_trim, split_clip_at, split_clip_at_scenes, get_keyframe_index, nearest_keyframes])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
 }
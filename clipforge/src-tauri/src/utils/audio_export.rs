@@ -0,0 +1,222 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::ffmpeg::{atempo_chain, clamp_speed};
+
+/// Audio file formats for extraction and audio-only export
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioFormat {
+    Wav,
+    Flac,
+    Aac,
+    Opus,
+    #[default]
+    Mp3,
+}
+
+impl AudioFormat {
+    /// File extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Aac => "m4a",
+            AudioFormat::Opus => "opus",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+
+    /// FFmpeg encoder for the format
+    pub fn codec(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "pcm_s16le",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Aac => "aac",
+            AudioFormat::Opus => "libopus",
+            AudioFormat::Mp3 => "libmp3lame",
+        }
+    }
+
+    /// Check an output path's extension matches the format (the muxer is picked from it)
+    pub fn check_output_path(&self, path: &str) -> Result<(), String> {
+        let extension = Path::new(path).extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase());
+        let matches = match extension.as_deref() {
+            Some("aac") => *self == AudioFormat::Aac,
+            Some(extension) => extension == self.extension(),
+            None => false,
+        };
+        if !matches {
+            return Err(format!("Output file {} doesn't match the export format. Use a .{} extension.", path, self.extension()));
+        }
+        Ok(())
+    }
+
+    /// Whether the format is lossless (bitrate does not apply)
+    pub fn is_lossless(&self) -> bool {
+        matches!(self, AudioFormat::Wav | AudioFormat::Flac)
+    }
+}
+
+/// Options for `extract_audio` / `export_audio` (None keeps the source's value)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct AudioExtractOptions {
+    #[serde(default)]
+    pub format: AudioFormat,
+    pub sample_rate: Option<u32>,  // Hz, e.g. 16000 or 48000
+    pub channels: Option<u32>,     // 1 = mono, 2 = stereo
+    pub bitrate: Option<String>,   // Lossy formats only, e.g. "192k"
+    pub start: Option<f64>,        // Extract only from this time (seconds)
+    pub end: Option<f64>,          // Extract only up to this time
+}
+
+/// Opus only supports these sample rates
+const OPUS_SAMPLE_RATES: [u32; 5] = [8000, 12000, 16000, 24000, 48000];
+
+impl AudioExtractOptions {
+    /// 16 kHz mono 128k MP3, as expected by Whisper-style speech-to-text APIs
+    pub fn transcription() -> Self {
+        Self {
+            format: AudioFormat::Mp3,
+            sample_rate: Some(16000),
+            channels: Some(1),
+            bitrate: Some("128k".to_string()),
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Check the options before running FFmpeg
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(rate) = self.sample_rate {
+            if !(8000..=192000).contains(&rate) {
+                return Err(format!("Unsupported sample rate: {} Hz", rate));
+            }
+            if self.format == AudioFormat::Opus && !OPUS_SAMPLE_RATES.contains(&rate) {
+                return Err(format!("Opus does not support {} Hz. Use 8000, 12000, 16000, 24000 or 48000.", rate));
+            }
+        }
+        if let Some(channels) = self.channels {
+            if !(1..=2).contains(&channels) {
+                return Err(format!("Unsupported channel count: {}. Use 1 (mono) or 2 (stereo).", channels));
+            }
+        }
+        match (self.start, self.end) {
+            (Some(start), _) if start < 0.0 => Err("Start time cannot be negative".to_string()),
+            (Some(start), Some(end)) if start >= end => Err("Start time must be less than end time".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Time range as (start, duration) for trimming, if any
+    pub fn range(&self) -> Option<(f64, Option<f64>)> {
+        match (self.start, self.end) {
+            (None, None) => None,
+            (start, end) => {
+                let start = start.unwrap_or(0.0);
+                Some((start, end.map(|e| e - start)))
+            }
+        }
+    }
+}
+
+/// First path in `dir` named `<stem>.<extension>`, `<stem>-1.<extension>`, ... that doesn't exist yet
+pub fn unique_output_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let mut candidate = dir.join(format!("{}.{}", stem, extension));
+    let mut index = 1;
+    while candidate.exists() {
+        candidate = dir.join(format!("{}-{}.{}", stem, index, extension));
+        index += 1;
+    }
+    candidate
+}
+
+/// Like `unique_output_path`, but creates the (empty) file so a concurrent caller
+/// can't pick the same name before it is written
+pub fn reserve_output_path(dir: &Path, stem: &str, extension: &str) -> Result<PathBuf, String> {
+    loop {
+        let candidate = unique_output_path(dir, stem, extension);
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&candidate) {
+            Ok(_) => return Ok(candidate),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {}", candidate.display(), e)),
+        }
+    }
+}
+
+/// A clip's audio in an audio-only timeline export
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioSegment {
+    pub duration: f64,          // Trimmed source duration (inputs are pre-trimmed with -ss/-t)
+    pub gain: f64,              // Linear gain (0.0 when muted)
+    pub speed: Option<f64>,
+    pub reverse: bool,
+    pub fade_in: Option<f64>,
+    pub fade_out: Option<f64>,
+}
+
+/// Label of the joined audio produced by `build_audio_concat_graph`
+pub const AUDIO_CONCAT_LABEL: &str = "[aout]";
+
+/// Build a filter_complex graph joining the audio of inputs 0..N in order,
+/// with each segment's retiming, gain and fades applied. The result is `[aout]`.
+pub fn build_audio_concat_graph(segments: &[AudioSegment]) -> String {
+    let mut chains = Vec::new();
+    push_concat(&mut chains, segments, 0, AUDIO_CONCAT_LABEL);
+    chains.join(";")
+}
+
+/// Build a graph for tracks that play at the same time: each track's segments are joined
+/// in order and the tracks are mixed into `[aout]`. Inputs are numbered across tracks,
+/// lowest track first.
+pub fn build_audio_tracks_graph(tracks: &[Vec<AudioSegment>]) -> String {
+    if let [segments] = tracks {
+        return build_audio_concat_graph(segments);
+    }
+
+    let mut chains = Vec::new();
+    let mut labels = Vec::new();
+    let mut first_input = 0;
+    for (t, segments) in tracks.iter().enumerate() {
+        let label = format!("[track{}]", t);
+        push_concat(&mut chains, segments, first_input, &label);
+        labels.push(label);
+        first_input += segments.len();
+    }
+    chains.push(format!(
+        "{}amix=inputs={}:duration=longest:normalize=0{}",
+        labels.concat(), labels.len(), AUDIO_CONCAT_LABEL
+    ));
+    chains.join(";")
+}
+
+// Chains for inputs first_input.. (one per segment), concatenated into `out`
+fn push_concat(chains: &mut Vec<String>, segments: &[AudioSegment], first_input: usize, out: &str) {
+    let inputs = first_input..first_input + segments.len();
+    for (i, segment) in inputs.clone().zip(segments) {
+        // Conform so concat sees matching formats
+        let mut filters = vec!["aformat=sample_rates=48000:channel_layouts=stereo".to_string()];
+        if segment.reverse {
+            filters.push("areverse".to_string());
+        }
+        if let Some(speed) = segment.speed {
+            filters.extend(atempo_chain(speed));
+        }
+        filters.push(format!("volume={}", segment.gain));
+
+        let output = segment.duration / segment.speed.map_or(1.0, clamp_speed);
+        if let Some(fade_in) = segment.fade_in.filter(|d| *d > 0.0) {
+            filters.push(format!("afade=t=in:st=0:d={}", fade_in.min(output)));
+        }
+        if let Some(fade_out) = segment.fade_out.filter(|d| *d > 0.0) {
+            let fade_out = fade_out.min(output);
+            filters.push(format!("afade=t=out:st={}:d={}", output - fade_out, fade_out));
+        }
+
+        chains.push(format!("[{}:a]{}[a{}]", i, filters.join(","), i));
+    }
+
+    let labels: String = inputs.map(|i| format!("[a{}]", i)).collect();
+    chains.push(format!("{}concat=n={}:v=0:a=1{}", labels, segments.len(), out));
+}
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+
+/// How an audio track participates in the mix
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioTrackRole {
+    #[default]
+    Music,    // Background bed, ducked under dialog when ducking is enabled
+    Dialog,   // Voiceover, mixed with the program audio and drives ducking
+    Effects,  // Sound effects, mixed at their own level and never ducked
+}
+
+/// An audio-only clip placed on the export timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioTrackClip {
+    pub path: String,
+    pub timeline_start: f64,  // Position on the program timeline in seconds
+    pub trim_start: f64,
+    pub trim_end: f64,
+    #[serde(default)]
+    pub role: AudioTrackRole,
+    pub gain_db: Option<f64>,  // Per-track gain in dB (max +20 dB)
+    pub fade_in: Option<f64>,
+    pub fade_out: Option<f64>,
+}
+
+/// Sidechain compression of music under dialog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuckingSettings {
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,  // Dialog level (0.0-1.0) above which music is ducked
+    #[serde(default = "default_ratio")]
+    pub ratio: f64,      // Compression ratio (1-20)
+    #[serde(default = "default_attack")]
+    pub attack_ms: f64,
+    #[serde(default = "default_release")]
+    pub release_ms: f64,
+}
+
+fn default_threshold() -> f64 {
+    0.05
+}
+
+fn default_ratio() -> f64 {
+    8.0
+}
+
+fn default_attack() -> f64 {
+    20.0
+}
+
+fn default_release() -> f64 {
+    400.0
+}
+
+impl Default for DuckingSettings {
+    fn default() -> Self {
+        Self {
+            threshold: default_threshold(),
+            ratio: default_ratio(),
+            attack_ms: default_attack(),
+            release_ms: default_release(),
+        }
+    }
+}
+
+impl DuckingSettings {
+    fn filter(&self) -> String {
+        format!(
+            "sidechaincompress=threshold={}:ratio={}:attack={}:release={}",
+            self.threshold.clamp(0.000976563, 1.0),
+            self.ratio.clamp(1.0, 20.0),
+            self.attack_ms.clamp(0.01, 2000.0),
+            self.release_ms.clamp(0.01, 9000.0)
+        )
+    }
+}
+
+/// Label of the mixed audio produced by `build_mix_graph`
+pub const MIX_OUTPUT_LABEL: &str = "[aout]";
+
+/// Build a filter_complex graph mixing the program audio (input 0) with audio
+/// track clips (inputs 1..=N, in order). The result is labelled `[aout]`.
+///
+/// - `program_duration`: length of the program; the mix never runs longer
+/// - `program_has_audio`: when false, silence stands in for the program audio
+/// - `tail_filter`: optional filter applied to the final mix (e.g. loudnorm)
+pub fn build_mix_graph(
+    tracks: &[AudioTrackClip],
+    ducking: Option<&DuckingSettings>,
+    program_duration: f64,
+    program_has_audio: bool,
+    tail_filter: Option<&str>,
+) -> String {
+    let mut chains = Vec::new();
+
+    let program_label = if program_has_audio {
+        "[0:a]".to_string()
+    } else {
+        chains.push(format!("aevalsrc=0:c=stereo:s=48000:d={}[prog]", program_duration));
+        "[prog]".to_string()
+    };
+
+    // Foreground (program + dialog), effects and music bed groups
+    let mut foreground = vec![program_label];
+    let mut effects = Vec::new();
+    let mut bed = Vec::new();
+
+    for (i, track) in tracks.iter().enumerate() {
+        let label = format!("[t{}]", i);
+        chains.push(format!("[{}:a]{}{}", i + 1, track_filters(track), label));
+
+        match track.role {
+            AudioTrackRole::Music => bed.push(label),
+            AudioTrackRole::Dialog => foreground.push(label),
+            AudioTrackRole::Effects => effects.push(label),
+        }
+    }
+
+    let mix_label = if tail_filter.is_some() { "[mix]" } else { MIX_OUTPUT_LABEL };
+
+    match ducking {
+        Some(settings) if !bed.is_empty() => {
+            // Only the program and dialog drive the sidechain
+            let fg = mix_group(&mut chains, &foreground, "[fg]", "first");
+            chains.push(format!("{}asplit=2[fgmix][sc]", fg));
+            let bed = mix_group(&mut chains, &bed, "[bed]", "longest");
+            chains.push(format!("{}[sc]{}[ducked]", bed, settings.filter()));
+            let all: Vec<String> = ["[fgmix]".to_string(), "[ducked]".to_string()].into_iter().chain(effects).collect();
+            chains.push(format!("{}amix=inputs={}:duration=first:normalize=0{}", all.concat(), all.len(), mix_label));
+        }
+        _ => {
+            let all: Vec<String> = foreground.into_iter().chain(effects).chain(bed).collect();
+            chains.push(format!("{}amix=inputs={}:duration=first:normalize=0{}", all.concat(), all.len(), mix_label));
+        }
+    }
+
+    if let Some(tail) = tail_filter {
+        chains.push(format!("[mix]{}{}", tail, MIX_OUTPUT_LABEL));
+    }
+
+    chains.join(";")
+}
+
+// Trim, level, fade and position a track clip on the program timeline
+fn track_filters(track: &AudioTrackClip) -> String {
+    let duration = (track.trim_end - track.trim_start).max(0.0);
+    let mut filters = vec![
+        format!("atrim=start={}:end={}", track.trim_start, track.trim_end),
+        "asetpts=PTS-STARTPTS".to_string(),
+    ];
+
+    if let Some(db) = track.gain_db.filter(|db| *db != 0.0) {
+        filters.push(format!("volume={}dB", db.clamp(super::ffmpeg::MIN_GAIN_DB, super::ffmpeg::MAX_GAIN_DB)));
+    }
+    if let Some(fade_in) = track.fade_in.filter(|d| *d > 0.0) {
+        filters.push(format!("afade=t=in:st=0:d={}", fade_in.min(duration)));
+    }
+    if let Some(fade_out) = track.fade_out.filter(|d| *d > 0.0) {
+        let fade_out = fade_out.min(duration);
+        filters.push(format!("afade=t=out:st={}:d={}", duration - fade_out, fade_out));
+    }
+
+    let delay_ms = (track.timeline_start.max(0.0) * 1000.0).round() as u64;
+    if delay_ms > 0 {
+        filters.push(format!("adelay={}:all=1", delay_ms));
+    }
+
+    filters.join(",")
+}
+
+// Mix a group of labels into one, returning the label to use downstream
+fn mix_group(chains: &mut Vec<String>, labels: &[String], out: &str, duration: &str) -> String {
+    if labels.len() == 1 {
+        return labels[0].clone();
+    }
+    chains.push(format!("{}amix=inputs={}:duration={}:normalize=0{}", labels.concat(), labels.len(), duration, out));
+    out.to_string()
+}
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+
+use super::detection::Interval;
+use super::ffmpeg::{check_reverse_duration, clamp_speed, ColorAdjustments, MAX_GAIN_DB, MIN_GAIN_DB};
+use super::subtitles::Cue;
+use super::text_overlay::TitleCard;
+use super::timeline::GAP_TOLERANCE;
+
+/// Per-clip video and audio effects, applied when the clip is rendered at export
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClipEffects {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,  // Audio volume (0.0-1.0, where 1.0 is 100%)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub muted: Option<bool>,  // Whether audio is muted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gain_db: Option<f64>,  // Audio gain in dB on top of volume (max +20 dB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_fade_in: Option<f64>,  // Audio fade-in duration in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_fade_out: Option<f64>,  // Audio fade-out duration in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_fade_in: Option<f64>,  // Fade from black duration in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_fade_out: Option<f64>,  // Fade to black duration in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorAdjustments>,  // Brightness/contrast/saturation/gamma
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lut_path: Option<String>,  // Path to .cube LUT file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,  // Playback speed factor (0.25-4.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<bool>,  // Play the clip backwards
+}
+
+impl ClipEffects {
+    /// Combined linear gain of volume and gain_db (0.0 when muted)
+    pub fn linear_gain(&self) -> f64 {
+        if self.is_muted() {
+            return 0.0;
+        }
+        let db = self.gain_db.unwrap_or(0.0).clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+        self.volume.unwrap_or(1.0).clamp(0.0, 1.0) * 10f64.powf(db / 20.0)
+    }
+
+    /// Playback speed clamped to the supported range (1.0 when unset)
+    pub fn speed_factor(&self) -> f64 {
+        self.speed.map_or(1.0, clamp_speed)
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted == Some(true)
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reverse == Some(true)
+    }
+
+    /// Names of the set effects that need their own filters (everything except volume/gain/mute)
+    pub fn filter_effects(&self) -> Vec<&'static str> {
+        [
+            ("speed", self.speed.is_some_and(|s| clamp_speed(s) != 1.0)),
+            ("reverse", self.is_reversed()),
+            ("audio fade in", self.audio_fade_in.is_some()),
+            ("audio fade out", self.audio_fade_out.is_some()),
+            ("video fade in", self.video_fade_in.is_some()),
+            ("video fade out", self.video_fade_out.is_some()),
+            ("color adjustments", self.color.is_some()),
+            ("LUT", self.lut_path.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, set)| set.then_some(name))
+        .collect()
+    }
+
+    /// Reject reversing a source range longer than MAX_REVERSE_DURATION
+    pub fn check_reverse(&self, source_range: f64) -> Result<(), String> {
+        if self.is_reversed() {
+            check_reverse_duration(source_range)?;
+        }
+        Ok(())
+    }
+}
+
+/// A non-destructive clip: a range of source media placed on the timeline.
+/// The source file is never rewritten; trims and effects are only rendered at export.
+/// Field names follow the frontend's workspace JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Clip {
+    pub id: String,
+    pub name: String,
+    pub path: String,  // Source media
+    pub start: f64,  // Timeline position of the clip's first frame
+    pub end: f64,  // Timeline position after the clip's last frame
+    pub duration: f64,  // Full source duration
+    #[serde(default)]
+    pub track: u32,
+    #[serde(rename = "trimStart")]
+    pub trim_start: f64,  // In point in source time
+    #[serde(rename = "trimEnd")]
+    pub trim_end: f64,  // Out point in source time
+    #[serde(flatten)]
+    pub effects: ClipEffects,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcription: Option<ClipTranscription>,
+    #[serde(flatten)]
+    pub media: MediaInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_card: Option<TitleCard>,  // Generated full-screen title instead of media
+}
+
+/// Probed details of the source, kept for display in the library
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MediaInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fps: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_rate: Option<u64>,
+}
+
+/// Transcript attached to a clip by the transcription panel (source time)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClipTranscription {
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub segments: Vec<Cue>,
+    #[serde(rename = "vttPath", default, skip_serializing_if = "Option::is_none")]
+    pub vtt_path: Option<String>,  // Caption file rendered at export
+    #[serde(default)]
+    pub language: String,
+}
+
+impl Clip {
+    /// Caption file for the clip, if it has been transcribed
+    pub fn captions_path(&self) -> Option<&str> {
+        self.transcription.as_ref().and_then(|t| t.vtt_path.as_deref())
+    }
+
+    /// Length of the clip once rendered (trim range adjusted for speed)
+    pub fn output_duration(&self) -> f64 {
+        (self.trim_end - self.trim_start) / self.effects.speed_factor()
+    }
+
+    /// Check the in/out points against the source duration. Stream-copied files often
+    /// end part of a frame early, so the out point may run past the end by GAP_TOLERANCE.
+    pub fn validate_trim(&self) -> Result<(), String> {
+        if self.trim_start < 0.0 || self.trim_end <= self.trim_start {
+            return Err(format!(
+                "Clip {} has an invalid trim range {:.3}-{:.3}s",
+                self.id, self.trim_start, self.trim_end
+            ));
+        }
+        if self.trim_end > self.duration + GAP_TOLERANCE {
+            return Err(format!(
+                "Clip {} is trimmed to {:.3}s but its source is only {:.3}s long",
+                self.id, self.trim_end, self.duration
+            ));
+        }
+        Ok(())
+    }
+
+    /// Move the in/out points without touching the source, keeping the timeline start
+    pub fn with_trim(&self, trim_start: f64, trim_end: f64) -> Result<Clip, String> {
+        let mut clip = Clip { trim_start, trim_end, ..self.clone() };
+        clip.validate_trim()?;
+        clip.end = clip.start + clip.output_duration();
+        Ok(clip)
+    }
+
+    /// Split at a timeline time into two clips referencing the same source.
+    /// The second half gets `second_id`.
+    pub fn split_at(&self, time: f64, second_id: &str) -> Result<(Clip, Clip), String> {
+        if time <= self.start || time >= self.end {
+            return Err(format!(
+                "Split point {:.3}s is outside clip {} ({:.3}-{:.3}s)",
+                time, self.id, self.start, self.end
+            ));
+        }
+
+        // Timeline time -> source time (a reversed clip plays its range backwards)
+        let elapsed = (time - self.start) * self.effects.speed_factor();
+        let source_time = if self.effects.is_reversed() {
+            self.trim_end - elapsed
+        } else {
+            self.trim_start + elapsed
+        };
+
+        let mut first = Clip { end: time, ..self.clone() };
+        let mut second = Clip { id: second_id.to_string(), start: time, ..self.clone() };
+        if self.effects.is_reversed() {
+            first.trim_start = source_time;
+            second.trim_end = source_time;
+        } else {
+            first.trim_end = source_time;
+            second.trim_start = source_time;
+        }
+
+        // Fades belong to the outer edges of the original clip
+        first.effects.video_fade_out = None;
+        first.effects.audio_fade_out = None;
+        second.effects.video_fade_in = None;
+        second.effects.audio_fade_in = None;
+
+        Ok((first, second))
+    }
+
+    /// Split into back-to-back sub-clips, one per source range (e.g. detected scenes).
+    /// The first sub-clip keeps the clip's id; the others get `{id_prefix}-{n}`.
+    pub fn split_into(&self, ranges: &[Interval], id_prefix: &str) -> Result<Vec<Clip>, String> {
+        if ranges.is_empty() {
+            return Err(format!("No ranges to split clip {} into", self.id));
+        }
+        if let Some(range) = ranges.iter().find(|r| {
+            r.start < self.trim_start - GAP_TOLERANCE || r.end > self.trim_end + GAP_TOLERANCE
+        }) {
+            return Err(format!(
+                "Range {:.3}-{:.3}s is outside clip {} ({:.3}-{:.3}s of source)",
+                range.start, range.end, self.id, self.trim_start, self.trim_end
+            ));
+        }
+
+        // A reversed clip plays its later ranges first
+        let mut ordered: Vec<&Interval> = ranges.iter().collect();
+        if self.effects.is_reversed() {
+            ordered.reverse();
+        }
+
+        let last = ordered.len() - 1;
+        let mut start = self.start;
+        let mut clips = Vec::with_capacity(ordered.len());
+        for (i, range) in ordered.into_iter().enumerate() {
+            let mut clip = Clip { start, ..self.clone() }.with_trim(range.start, range.end)?;
+            // Fades stay on the outer edges of the original clip
+            if i > 0 {
+                clip.id = format!("{}-{}", id_prefix, i);
+                clip.effects.video_fade_in = None;
+                clip.effects.audio_fade_in = None;
+            }
+            if i < last {
+                clip.effects.video_fade_out = None;
+                clip.effects.audio_fade_out = None;
+            }
+            start = clip.end;
+            clips.push(clip);
+        }
+        Ok(clips)
+    }
+}
@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+
+use super::ffmpeg::scale_pad_filter;
+use super::overlay::{even_dimension, Anchor};
+use super::transitions::TRANSITION_FPS;
+
+/// Layouts that composite several clips into one frame instead of playing them in sequence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ExportLayout {
+    PictureInPicture(PipSettings),  // Clip 0 full frame, clip 1 overlaid
+    SideBySide(StackSettings),      // Clips in a row, left to right
+    Stacked(StackSettings),         // Clips in a column, top to bottom
+    Grid(GridSettings),             // Clips in rows of `columns`, left to right then top to bottom
+}
+
+/// How clips of different lengths are synced in a stacked/grid layout
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationSync {
+    #[default]
+    Shortest,  // Stop when the first clip ends
+    Longest,   // Hold the last frame of shorter clips until the longest ends
+}
+
+/// Settings for side-by-side and stacked layouts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct StackSettings {
+    #[serde(default)]
+    pub sync: DurationSync,
+}
+
+/// Settings for grid layouts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct GridSettings {
+    pub columns: Option<u32>,  // Defaults to a square-ish grid (2 for 2-4 clips, 3 for 5-9)
+    #[serde(default)]
+    pub sync: DurationSync,
+}
+
+/// Most clips a stacked layout accepts (cells get too small beyond this)
+pub const MAX_STACK_INPUTS: usize = 4;
+
+/// Most clips a grid layout accepts
+pub const MAX_GRID_INPUTS: usize = 9;
+
+/// Which clip audio a picture-in-picture export keeps
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PipAudio {
+    #[default]
+    Primary,
+    Secondary,
+    Mix,
+}
+
+/// Placement and styling of the secondary (e.g. webcam) clip
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipSettings {
+    #[serde(default)]
+    pub anchor: Anchor,
+    pub x: Option<u32>,  // Custom position in pixels (overrides anchor when x and y are set)
+    pub y: Option<u32>,
+    #[serde(default = "default_margin")]
+    pub margin: u32,
+    #[serde(default = "default_scale")]
+    pub scale: f64,      // PiP width as a fraction of the output width
+    #[serde(default)]
+    pub border_width: u32,
+    #[serde(default = "default_border_color")]
+    pub border_color: String,
+    #[serde(default)]
+    pub corner_radius: u32,
+    #[serde(default)]
+    pub audio: PipAudio,
+}
+
+fn default_margin() -> u32 {
+    32
+}
+
+fn default_scale() -> f64 {
+    0.25
+}
+
+fn default_border_color() -> String {
+    "white".to_string()
+}
+
+/// A clip feeding a composition, as input `index` of the FFmpeg command
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutInput {
+    pub duration: f64,
+    pub has_audio: bool,
+    pub gain: f64,  // Linear gain (0.0 when muted)
+}
+
+/// Filter graph for a composition with its output labels
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutGraph {
+    pub graph: String,
+    pub audio_label: Option<String>,  // None when no input contributes audio
+    pub duration: f64,
+}
+
+/// Label of the composed video produced by layout graphs
+pub const LAYOUT_VIDEO_LABEL: &str = "[vout]";
+
+// Alpha mask rounding the corners of a frame with radius r
+fn rounded_corners_filter(radius: u32) -> String {
+    let r = radius;
+    format!(
+        "format=yuva420p,geq=lum='p(X,Y)':cb='p(X,Y)':cr='p(X,Y)':\
+         a='if(gt(abs(W/2-X),W/2-{r})*gt(abs(H/2-Y),H/2-{r}),if(lte(hypot({r}-(W/2-abs(W/2-X)),{r}-(H/2-abs(H/2-Y))),{r}),255,0),255)'",
+        r = r
+    )
+}
+
+// Mix the audio of the given inputs into `out`, applying each input's gain
+fn mix_inputs(chains: &mut Vec<String>, inputs: &[LayoutInput], indices: &[usize], duration: &str, out: &str) -> Option<String> {
+    let sources: Vec<usize> = indices.iter().copied().filter(|i| inputs[*i].has_audio && inputs[*i].gain > 0.0).collect();
+    if sources.is_empty() {
+        return None;
+    }
+
+    let mut labels = Vec::new();
+    for i in &sources {
+        chains.push(format!("[{}:a]volume={}[ain{}]", i, inputs[*i].gain, i));
+        labels.push(format!("[ain{}]", i));
+    }
+
+    if labels.len() == 1 {
+        chains.push(format!("{}anull{}", labels[0], out));
+    } else {
+        chains.push(format!("{}amix=inputs={}:duration={}:normalize=0{}", labels.concat(), labels.len(), duration, out));
+    }
+    Some(out.to_string())
+}
+
+/// Build the picture-in-picture graph: input 0 fills the frame, input 1 is overlaid.
+/// The output runs for the primary clip's duration; the PiP disappears if it ends first.
+pub fn build_pip_graph(settings: &PipSettings, width: u32, height: u32, inputs: &[LayoutInput]) -> LayoutGraph {
+    let mut chains = Vec::new();
+    chains.push(format!("[0:v]{},setsar=1[base]", scale_pad_filter(width, height)));
+
+    let pip_width = even_dimension(width as f64 * settings.scale.clamp(0.05, 1.0));
+    let mut pip = vec![format!("scale={}:-2", pip_width), "setsar=1".to_string()];
+    if settings.border_width > 0 {
+        let b = settings.border_width;
+        pip.push(format!("pad=iw+{}:ih+{}:{}:{}:color={}", b * 2, b * 2, b, b, settings.border_color));
+    }
+    if settings.corner_radius > 0 {
+        pip.push(rounded_corners_filter(settings.corner_radius));
+    }
+    chains.push(format!("[1:v]{}[pip]", pip.join(",")));
+
+    let (x, y) = match (settings.x, settings.y) {
+        (Some(x), Some(y)) => (x.to_string(), y.to_string()),
+        _ => settings.anchor.overlay_position(settings.margin),
+    };
+    chains.push(format!("[base][pip]overlay=x={}:y={}:eof_action=pass,format=yuv420p{}", x, y, LAYOUT_VIDEO_LABEL));
+
+    let audio_indices: &[usize] = match settings.audio {
+        PipAudio::Primary => &[0],
+        PipAudio::Secondary => &[1],
+        PipAudio::Mix => &[0, 1],
+    };
+    let audio_label = mix_inputs(&mut chains, inputs, audio_indices, "first", "[aout]");
+
+    LayoutGraph {
+        graph: chains.join(";"),
+        audio_label,
+        duration: inputs[0].duration,
+    }
+}
+
+/// Arrangement of the cells in a stacked or grid layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StackDirection {
+    Horizontal,
+    Vertical,
+    Grid(u32),  // Number of columns
+}
+
+/// Columns used by a grid when none are given
+pub fn default_grid_columns(count: usize) -> u32 {
+    (count as f64).sqrt().ceil().max(1.0) as u32
+}
+
+/// Build a stacked or grid graph from inputs 0..N. Each input is normalized to
+/// its cell size and frame rate; the composed frame is padded to the output size.
+pub fn build_stack_graph(direction: StackDirection, sync: DurationSync, width: u32, height: u32, inputs: &[LayoutInput]) -> LayoutGraph {
+    let count = inputs.len() as u32;
+    let (columns, rows) = match direction {
+        StackDirection::Horizontal => (count, 1),
+        StackDirection::Vertical => (1, count),
+        StackDirection::Grid(columns) => {
+            let columns = columns.clamp(1, count.max(1));
+            (columns, count.div_ceil(columns))
+        }
+    };
+    let cell_width = even_dimension(width as f64 / columns as f64);
+    let cell_height = even_dimension(height as f64 / rows as f64);
+
+    let duration = match sync {
+        DurationSync::Shortest => inputs.iter().map(|i| i.duration).fold(f64::INFINITY, f64::min),
+        DurationSync::Longest => inputs.iter().map(|i| i.duration).fold(0.0, f64::max),
+    };
+
+    let mut chains = Vec::new();
+    let mut cells = Vec::new();
+    for (i, input) in inputs.iter().enumerate() {
+        let mut filters = vec![
+            scale_pad_filter(cell_width, cell_height),
+            "setsar=1".to_string(),
+            format!("fps={}", TRANSITION_FPS),
+        ];
+        // Hold the last frame so every cell runs for the full duration
+        if sync == DurationSync::Longest && input.duration < duration {
+            filters.push(format!("tpad=stop_mode=clone:stop_duration={}", duration - input.duration));
+        }
+        chains.push(format!("[{}:v]{}[c{}]", i, filters.join(","), i));
+        cells.push(format!("[c{}]", i));
+    }
+
+    let stack = match direction {
+        StackDirection::Horizontal => format!("hstack=inputs={}:shortest=1", count),
+        StackDirection::Vertical => format!("vstack=inputs={}:shortest=1", count),
+        StackDirection::Grid(_) => {
+            let positions: Vec<String> = (0..count)
+                .map(|i| format!("{}_{}", (i % columns) * cell_width, (i / columns) * cell_height))
+                .collect();
+            format!("xstack=inputs={}:layout={}:fill=black:shortest=1", count, positions.join("|"))
+        }
+    };
+    chains.push(format!(
+        "{}{},{},setsar=1,format=yuv420p{}",
+        cells.concat(), stack, scale_pad_filter(width, height), LAYOUT_VIDEO_LABEL
+    ));
+
+    // Audio is padded/trimmed to exactly the video duration
+    let indices: Vec<usize> = (0..inputs.len()).collect();
+    let mix_duration = match sync {
+        DurationSync::Shortest => "shortest",
+        DurationSync::Longest => "longest",
+    };
+    let audio_label = mix_inputs(&mut chains, inputs, &indices, mix_duration, "[amix]").map(|mix| {
+        chains.push(format!("{}apad=whole_dur={},atrim=end={}[aout]", mix, duration, duration));
+        "[aout]".to_string()
+    });
+
+    LayoutGraph {
+        graph: chains.join(";"),
+        audio_label,
+        duration,
+    }
+}
+
+impl ExportLayout {
+    /// Check that the layout can compose `count` clips
+    pub fn check_input_count(&self, count: usize) -> Result<(), String> {
+        let (min, max) = match self {
+            ExportLayout::PictureInPicture(_) => (2, 2),
+            ExportLayout::SideBySide(_) | ExportLayout::Stacked(_) => (2, MAX_STACK_INPUTS),
+            ExportLayout::Grid(_) => (2, MAX_GRID_INPUTS),
+        };
+        if count < min || count > max {
+            if min == max {
+                return Err(format!("This layout needs exactly {} clips, got {}", min, count));
+            }
+            return Err(format!("This layout needs {} to {} clips, got {}", min, max, count));
+        }
+        Ok(())
+    }
+
+    /// Build the filter graph for this layout at the given output size
+    pub fn build_graph(&self, width: u32, height: u32, inputs: &[LayoutInput]) -> LayoutGraph {
+        match self {
+            ExportLayout::PictureInPicture(settings) => build_pip_graph(settings, width, height, inputs),
+            ExportLayout::SideBySide(settings) => {
+                build_stack_graph(StackDirection::Horizontal, settings.sync, width, height, inputs)
+            }
+            ExportLayout::Stacked(settings) => {
+                build_stack_graph(StackDirection::Vertical, settings.sync, width, height, inputs)
+            }
+            ExportLayout::Grid(settings) => {
+                let columns = settings.columns.unwrap_or_else(|| default_grid_columns(inputs.len()));
+                build_stack_graph(StackDirection::Grid(columns), settings.sync, width, height, inputs)
+            }
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use serde::{Deserialize, Serialize};
+
+/// A detected time range in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Interval {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl Interval {
+    /// Length in seconds
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+// Value following `key` in an FFmpeg log line, up to the next whitespace or '|'
+fn value_after(line: &str, key: &str) -> Option<f64> {
+    let rest = &line[line.find(key)? + key.len()..];
+    rest.trim_start()
+        .split(|c: char| c.is_whitespace() || c == '|')
+        .next()?
+        .parse()
+        .ok()
+}
+
+// Pair up `<prefix>_start` / `<prefix>_end` values from a detection filter's log lines
+// (on separate lines or the same one). Times are relative to the analyzed range;
+// an unterminated interval ends at `duration`.
+fn parse_intervals(stderr: &str, filter: &str, start_key: &str, end_key: &str, duration: Option<f64>) -> Vec<Interval> {
+    let mut intervals = Vec::new();
+    let mut open: Option<f64> = None;
+
+    for line in stderr.lines().filter(|l| l.contains(filter)) {
+        if let Some(start) = value_after(line, start_key) {
+            open = Some(start.max(0.0));
+        }
+        if let Some(end) = value_after(line, end_key) {
+            if let Some(start) = open.take() {
+                intervals.push(Interval { start, end });
+            }
+        }
+    }
+
+    if let (Some(start), Some(end)) = (open, duration) {
+        if end > start {
+            intervals.push(Interval { start, end });
+        }
+    }
+
+    intervals
+}
+
+/// silencedetect parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SilenceSettings {
+    #[serde(default = "default_noise_db")]
+    pub threshold_db: f64,  // Audio below this level counts as silence
+    #[serde(default = "default_min_silence")]
+    pub min_duration: f64,  // Shortest pause reported, in seconds
+}
+
+fn default_noise_db() -> f64 {
+    -30.0
+}
+
+fn default_min_silence() -> f64 {
+    0.5
+}
+
+impl Default for SilenceSettings {
+    fn default() -> Self {
+        Self {
+            threshold_db: default_noise_db(),
+            min_duration: default_min_silence(),
+        }
+    }
+}
+
+impl SilenceSettings {
+    /// Build the silencedetect filter
+    pub fn filter(&self) -> String {
+        format!(
+            "silencedetect=noise={}dB:d={}",
+            self.threshold_db.clamp(-90.0, 0.0),
+            self.min_duration.max(0.01)
+        )
+    }
+}
+
+/// Parse silencedetect output into silence intervals
+pub fn parse_silencedetect(stderr: &str, duration: Option<f64>) -> Vec<Interval> {
+    parse_intervals(stderr, "silencedetect", "silence_start:", "silence_end:", duration)
+}
+
+/// Ranges of `start..end` to keep once `silences` are cut out. Each silence is shrunk by
+/// `padding` where it borders speech so words aren't clipped (silence at the range edges
+/// is cut entirely); keeps shorter than `min_keep` are dropped.
+pub fn keep_segments(silences: &[Interval], start: f64, end: f64, padding: f64, min_keep: f64) -> Vec<Interval> {
+    let padding = padding.max(0.0);
+    let mut keeps = Vec::new();
+    let mut position = start;
+
+    let mut cuts: Vec<Interval> = silences.iter()
+        .map(|s| Interval {
+            start: if s.start <= start { s.start } else { s.start + padding },
+            end: if s.end >= end { s.end } else { s.end - padding },
+        })
+        .filter(|s| s.end > s.start)
+        .collect();
+    cuts.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    for cut in cuts {
+        let cut_start = cut.start.clamp(start, end);
+        let cut_end = cut.end.clamp(start, end);
+        if cut_start > position {
+            keeps.push(Interval { start: position, end: cut_start });
+        }
+        position = position.max(cut_end);
+    }
+    if end > position {
+        keeps.push(Interval { start: position, end });
+    }
+
+    keeps.retain(|k| k.duration() >= min_keep);
+    keeps
+}
+
+/// Scene-change detection parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneSettings {
+    #[serde(default = "default_scene_threshold")]
+    pub threshold: f64,         // Scene score (0.0-1.0) above which a frame starts a new scene
+    #[serde(default = "default_min_scene_length")]
+    pub min_scene_length: f64,  // Cuts closer than this to the previous one are ignored
+}
+
+fn default_scene_threshold() -> f64 {
+    0.3
+}
+
+fn default_min_scene_length() -> f64 {
+    1.0
+}
+
+impl Default for SceneSettings {
+    fn default() -> Self {
+        Self {
+            threshold: default_scene_threshold(),
+            min_scene_length: default_min_scene_length(),
+        }
+    }
+}
+
+impl SceneSettings {
+    /// Select scene-change frames, log their score (metadata) and timestamp (showinfo)
+    pub fn filter(&self) -> String {
+        format!(
+            "select='gt(scene,{})',metadata=print:key=lavfi.scene_score,showinfo",
+            self.threshold.clamp(0.0, 1.0)
+        )
+    }
+}
+
+/// A detected cut: the first frame of a new scene
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SceneCut {
+    pub time: f64,
+    pub score: Option<f64>,  // Scene score of the frame (0.0-1.0)
+}
+
+/// Parse scene cuts from the output of `SceneSettings::filter`. metadata prints each
+/// selected frame's score just before showinfo reports the same frame.
+pub fn parse_scene_cuts(stderr: &str) -> Vec<SceneCut> {
+    let mut cuts = Vec::new();
+    let mut score = None;
+
+    for line in stderr.lines() {
+        if line.contains("lavfi.scene_score=") {
+            score = value_after(line, "lavfi.scene_score=");
+        } else if line.contains("showinfo") {
+            if let Some(time) = value_after(line, "pts_time:") {
+                cuts.push(SceneCut { time, score: score.take() });
+            }
+        }
+    }
+
+    cuts
+}
+
+/// Drop cuts within `min_length` of the range start, the previous kept cut or the range end
+pub fn filter_scene_cuts(cuts: &[SceneCut], start: f64, end: f64, min_length: f64) -> Vec<SceneCut> {
+    let mut kept: Vec<SceneCut> = Vec::new();
+    let mut previous = start;
+
+    for cut in cuts {
+        if cut.time - previous >= min_length && end - cut.time >= min_length {
+            kept.push(*cut);
+            previous = cut.time;
+        }
+    }
+
+    kept
+}
+
+/// Split `start..end` into consecutive ranges at the given cut times
+pub fn split_at(cuts: &[f64], start: f64, end: f64) -> Vec<Interval> {
+    let mut points: Vec<f64> = cuts.iter().copied().filter(|t| *t > start && *t < end).collect();
+    points.sort_by(|a, b| a.total_cmp(b));
+    points.dedup();
+
+    let mut ranges = Vec::new();
+    let mut position = start;
+    for point in points {
+        ranges.push(Interval { start: position, end: point });
+        position = point;
+    }
+    ranges.push(Interval { start: position, end });
+    ranges
+}
+
+/// Black and frozen frame detection parameters for a QC pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityCheckSettings {
+    #[serde(default = "default_black_min_duration")]
+    pub black_min_duration: f64,  // Shortest black segment reported, in seconds
+    #[serde(default = "default_black_pixel_threshold")]
+    pub black_pixel_threshold: f64,  // Luminance (0.0-1.0) below which a pixel is black
+    #[serde(default = "default_black_picture_ratio")]
+    pub black_picture_ratio: f64,  // Share of black pixels for a frame to count as black
+    #[serde(default = "default_freeze_noise_db")]
+    pub freeze_noise_db: f64,  // Frame difference below this counts as frozen
+    #[serde(default = "default_freeze_min_duration")]
+    pub freeze_min_duration: f64,  // Shortest freeze reported, in seconds
+}
+
+fn default_black_min_duration() -> f64 {
+    0.5
+}
+
+fn default_black_pixel_threshold() -> f64 {
+    0.1
+}
+
+fn default_black_picture_ratio() -> f64 {
+    0.98
+}
+
+fn default_freeze_noise_db() -> f64 {
+    -60.0
+}
+
+fn default_freeze_min_duration() -> f64 {
+    2.0
+}
+
+impl Default for QualityCheckSettings {
+    fn default() -> Self {
+        Self {
+            black_min_duration: default_black_min_duration(),
+            black_pixel_threshold: default_black_pixel_threshold(),
+            black_picture_ratio: default_black_picture_ratio(),
+            freeze_noise_db: default_freeze_noise_db(),
+            freeze_min_duration: default_freeze_min_duration(),
+        }
+    }
+}
+
+impl QualityCheckSettings {
+    /// blackdetect and freezedetect in one video chain
+    pub fn filter(&self) -> String {
+        format!(
+            "blackdetect=d={}:pix_th={}:pic_th={},freezedetect=n={}dB:d={}",
+            self.black_min_duration.max(0.01),
+            self.black_pixel_threshold.clamp(0.0, 1.0),
+            self.black_picture_ratio.clamp(0.0, 1.0),
+            self.freeze_noise_db.clamp(-90.0, 0.0),
+            self.freeze_min_duration.max(0.01)
+        )
+    }
+}
+
+/// Segments flagged by a QC pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct QualityReport {
+    pub black: Vec<Interval>,
+    pub frozen: Vec<Interval>,
+}
+
+impl QualityReport {
+    /// Shift every interval by `offset` seconds (e.g. from seek-relative to source time)
+    pub fn offset(self, offset: f64) -> Self {
+        let shift = |intervals: Vec<Interval>| {
+            intervals.into_iter()
+                .map(|i| Interval { start: i.start + offset, end: i.end + offset })
+                .collect()
+        };
+        Self {
+            black: shift(self.black),
+            frozen: shift(self.frozen),
+        }
+    }
+
+    /// Whether nothing was flagged
+    pub fn is_clean(&self) -> bool {
+        self.black.is_empty() && self.frozen.is_empty()
+    }
+}
+
+/// Parse blackdetect and freezedetect output
+pub fn parse_quality_report(stderr: &str, duration: Option<f64>) -> QualityReport {
+    QualityReport {
+        black: parse_intervals(stderr, "blackdetect", "black_start:", "black_end:", duration),
+        frozen: parse_intervals(stderr, "freezedetect", "freeze_start:", "freeze_end:", duration),
+    }
+}
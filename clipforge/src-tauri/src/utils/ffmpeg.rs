@@ -3,6 +3,8 @@ use std::process::Stdio;
 use tokio::io::{AsyncBufReadExt, BufReader as AsyncBufReader};
 use tauri::{Manager, Emitter};
 
+use super::text_overlay::TextOverlay;
+
 
 
 impl std::fmt::Display for FFmpegError {
@@ -12,6 +14,7 @@ impl std::fmt::Display for FFmpegError {
             FFmpegError::ExecutionFailed(msg) => write!(f, "FFmpeg execution failed: {}", msg),
             FFmpegError::OutputValidation(msg) => write!(f, "Output validation failed: {}", msg),
             FFmpegError::InvalidPath(msg) => write!(f, "Invalid path: {}", msg),
+            FFmpegError::InvalidOptions(msg) => write!(f, "Invalid FFmpeg options: {}", msg),
         }
     }
 }
@@ -23,6 +26,7 @@ pub enum FFmpegError {
     ExecutionFailed(String),
     OutputValidation(String),
     InvalidPath(String),
+    InvalidOptions(String),
 }
 
 impl std::error::Error for FFmpegError {}
@@ -30,6 +34,59 @@ impl std::error::Error for FFmpegError {}
 /// Result type for FFmpeg operations
 pub type FFmpegResult<T> = Result<T, FFmpegError>;
 
+/// Maximum boost allowed by `FfmpegBuilder::gain_db` (+20 dB = 10x amplitude)
+pub const MAX_GAIN_DB: f64 = 20.0;
+
+/// Maximum cut allowed by `FfmpegBuilder::gain_db`
+pub const MIN_GAIN_DB: f64 = -60.0;
+
+/// Slowest playback speed allowed by `FfmpegBuilder::speed`
+pub const MIN_SPEED: f64 = 0.25;
+
+/// Fastest playback speed allowed by `FfmpegBuilder::speed`
+pub const MAX_SPEED: f64 = 4.0;
+
+/// Longest range `FfmpegBuilder::reverse` will play backwards. reverse/areverse
+/// buffer every decoded frame in memory, so longer ranges can exhaust RAM.
+pub const MAX_REVERSE_DURATION: f64 = 120.0;
+
+/// Clamp a speed factor to MIN_SPEED..=MAX_SPEED (non-finite values mean normal speed)
+pub fn clamp_speed(speed: f64) -> f64 {
+    if speed.is_finite() { speed.clamp(MIN_SPEED, MAX_SPEED) } else { 1.0 }
+}
+
+/// Reject reversing a source range longer than MAX_REVERSE_DURATION
+pub fn check_reverse_duration(source_range: f64) -> Result<(), String> {
+    if source_range > MAX_REVERSE_DURATION {
+        return Err(format!(
+            "Reverse is limited to {:.0}s of source ({:.1}s selected); trim the clip first",
+            MAX_REVERSE_DURATION, source_range
+        ));
+    }
+    Ok(())
+}
+
+/// Split a speed factor into atempo stages, each within atempo's 0.5-2.0 range.
+/// atempo time-stretches without changing pitch.
+pub fn atempo_chain(speed: f64) -> Vec<String> {
+    let mut remaining = clamp_speed(speed);
+    let mut stages = Vec::new();
+
+    while remaining > 2.0 {
+        stages.push("atempo=2".to_string());
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 {
+        stages.push("atempo=0.5".to_string());
+        remaining /= 0.5;
+    }
+    if (remaining - 1.0).abs() > f64::EPSILON {
+        stages.push(format!("atempo={}", remaining));
+    }
+
+    stages
+}
+
 /// Builder for constructing FFmpeg commands with fluent API
 #[derive(Default)]
 pub struct FfmpegBuilder {
@@ -58,7 +115,93 @@ pub struct FfmpegBuilder {
     scale_pad: bool,  // Whether to pad to maintain aspect ratio
     volume: Option<f64>,  // Audio volume (0.0-1.0, where 1.0 is 100%)
     muted: bool,  // Whether audio should be muted
+    gain_db: Option<f64>,  // Audio gain in dB, applied after volume
+    audio_fade_in: Option<f64>,  // Audio fade-in duration in seconds
+    audio_fade_out: Option<f64>,  // Audio fade-out duration in seconds (needs trim duration)
     timeout_secs: Option<u64>,  // Command timeout in seconds
+    loudnorm_filter: Option<String>,  // loudnorm filter (measurement or normalization pass)
+    audio_sample_rate: Option<u32>,  // Output audio sample rate (-ar)
+    audio_channels: Option<u32>,  // Output audio channel count (-ac)
+    audio_only: bool,  // Drop the video stream (-vn)
+    video_only: bool,  // Drop the audio stream (-an)
+    copy_video: bool,  // Copy video stream, re-encode audio only
+    null_output: bool,  // Discard output (analysis passes)
+    extra_inputs: Vec<InputSpec>,  // Additional inputs after the main input (indices 1..)
+    filter_complex: Option<String>,  // Replaces -vf/-af when set
+    maps: Vec<String>,  // Output stream mappings for filter_complex
+    video_fade_in: Option<f64>,  // Fade from black duration in seconds
+    video_fade_out: Option<f64>,  // Fade to black duration in seconds (needs trim duration)
+    color: Option<ColorAdjustments>,  // eq filter parameters
+    lut_path: Option<String>,  // .cube LUT applied with lut3d
+    speed: Option<f64>,  // Playback speed factor (0.25-4.0)
+    reverse: bool,  // Play video and audio backwards
+    input_format: Option<String>,  // Force main input format (-f), e.g. lavfi
+    text_overlays: Vec<TextOverlay>,  // drawtext layers
+    shortest: bool,  // Stop at the end of the shortest stream
+    subtitles_filter: Option<String>,  // Burned-in captions (subtitles filter)
+    subtitle_codec: Option<String>,  // Subtitle stream codec (-c:s)
+    custom_audio_filters: Vec<String>,  // Extra audio filters (e.g. silencedetect), appended to the chain
+    custom_video_filters: Vec<String>,  // Extra video filters (e.g. scene select), appended to the chain
+    stream_metadata: Vec<(String, String)>,  // Per-stream metadata (-metadata:<spec> key=value)
+    output_options: Vec<String>,  // Extra output options, added just before the output path
+}
+
+/// Basic color correction applied with the eq filter (None leaves a parameter at its default)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ColorAdjustments {
+    pub brightness: Option<f64>,  // -1.0 to 1.0 (default 0)
+    pub contrast: Option<f64>,    // 0.0 to 3.0 (default 1)
+    pub saturation: Option<f64>,  // 0.0 to 3.0 (default 1)
+    pub gamma: Option<f64>,       // 0.1 to 10.0 (default 1)
+}
+
+impl ColorAdjustments {
+    /// Build the eq filter, or None when every parameter is at its default
+    pub fn eq_filter(&self) -> Option<String> {
+        let params: Vec<String> = [
+            ("brightness", self.brightness.map(|v| v.clamp(-1.0, 1.0)), 0.0),
+            ("contrast", self.contrast.map(|v| v.clamp(0.0, 3.0)), 1.0),
+            ("saturation", self.saturation.map(|v| v.clamp(0.0, 3.0)), 1.0),
+            ("gamma", self.gamma.map(|v| v.clamp(0.1, 10.0)), 1.0),
+        ]
+        .iter()
+        .filter_map(|(name, value, default)| {
+            value.filter(|v| v != default).map(|v| format!("{}={}", name, v))
+        })
+        .collect();
+
+        if params.is_empty() {
+            None
+        } else {
+            Some(format!("eq={}", params.join(":")))
+        }
+    }
+}
+
+/// Scale to fit within width x height keeping aspect ratio, padding the rest with black
+pub fn scale_pad_filter(width: u32, height: u32) -> String {
+    format!(
+        "scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black",
+        width, height, width, height
+    )
+}
+
+/// Quote a value (typically a file path) for use as a filter option inside a filter graph.
+/// Backslashes become forward slashes, colons are escaped for the option parser and
+/// single quotes are escaped for both the graph and option parsers.
+pub fn escape_filter_value(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "/")
+        .replace(':', "\\:")
+        .replace('\'', "'\\\\\\''");
+    format!("'{}'", escaped)
+}
+
+/// Additional input with its own input options (e.g. `-loop 1`, `-f lavfi`)
+#[derive(Clone)]
+pub struct InputSpec {
+    pub options: Vec<String>,
+    pub path: String,
 }
 
 #[derive(Clone)]
@@ -86,6 +229,12 @@ impl FfmpegBuilder {
         self
     }
 
+    /// Seek the input to `start` without limiting the duration
+    pub fn seek(mut self, start: f64) -> Self {
+        self.trim_start = Some(start);
+        self
+    }
+
     /// Set trim parameters (start time and duration)
     pub fn trim(mut self, start: f64, duration: f64) -> Self {
         self.trim_start = Some(start);
@@ -194,6 +343,12 @@ impl FfmpegBuilder {
         self
     }
 
+    /// Add raw output options (e.g. `-bsf:v h264_mp4toannexb`), placed just before the output
+    pub fn output_options(mut self, options: &[&str]) -> Self {
+        self.output_options.extend(options.iter().map(|o| o.to_string()));
+        self
+    }
+
     /// Set app handle for sidecar binary resolution
     pub fn with_app_handle(mut self, handle: tauri::AppHandle) -> Self {
         self.app_handle = Some(handle);
@@ -206,6 +361,102 @@ impl FfmpegBuilder {
         self
     }
 
+    /// Set audio gain in dB (clamped to MIN_GAIN_DB..=MAX_GAIN_DB), allowing quiet clips to be boosted
+    pub fn gain_db(mut self, db: f64) -> Self {
+        self.gain_db = Some(db.clamp(MIN_GAIN_DB, MAX_GAIN_DB));
+        self
+    }
+
+    /// Fade audio in from silence over the given duration
+    pub fn audio_fade_in(mut self, duration: f64) -> Self {
+        self.audio_fade_in = Some(duration.max(0.0));
+        self
+    }
+
+    /// Fade audio out to silence over the given duration, ending at the trim end
+    pub fn audio_fade_out(mut self, duration: f64) -> Self {
+        self.audio_fade_out = Some(duration.max(0.0));
+        self
+    }
+
+    /// Fade video in from black over the given duration
+    pub fn video_fade_in(mut self, duration: f64) -> Self {
+        self.video_fade_in = Some(duration.max(0.0));
+        self
+    }
+
+    /// Fade video out to black over the given duration, ending at the trim end
+    pub fn video_fade_out(mut self, duration: f64) -> Self {
+        self.video_fade_out = Some(duration.max(0.0));
+        self
+    }
+
+    /// Apply brightness/contrast/saturation/gamma adjustments
+    pub fn color_adjust(mut self, color: ColorAdjustments) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Apply a 3D LUT (.cube file)
+    pub fn lut3d(mut self, path: &str) -> Self {
+        self.lut_path = Some(path.to_string());
+        self
+    }
+
+    /// Change playback speed (clamped to MIN_SPEED..=MAX_SPEED), preserving audio pitch
+    pub fn speed(mut self, factor: f64) -> Self {
+        self.speed = Some(clamp_speed(factor));
+        self
+    }
+
+    /// Play the trimmed range backwards
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Force the main input format (e.g. "lavfi" for generated sources)
+    pub fn input_format(mut self, format: &str) -> Self {
+        self.input_format = Some(format.to_string());
+        self
+    }
+
+    /// Burn a text layer into the video
+    pub fn text_overlay(mut self, overlay: TextOverlay) -> Self {
+        self.text_overlays.push(overlay);
+        self
+    }
+
+    /// Burn captions with a prebuilt subtitles filter. Cue times are in source time,
+    /// so the trim offset is compensated for automatically.
+    pub fn burn_subtitles(mut self, filter: &str) -> Self {
+        self.subtitles_filter = Some(filter.to_string());
+        self
+    }
+
+    /// Encode subtitle streams with the given codec (e.g. mov_text, srt)
+    pub fn subtitle_codec(mut self, codec: &str) -> Self {
+        self.subtitle_codec = Some(codec.to_string());
+        self
+    }
+
+    /// Tag an output stream, e.g. `stream_metadata("s:s:0", "language=eng")`
+    pub fn stream_metadata(mut self, stream: &str, entry: &str) -> Self {
+        self.stream_metadata.push((stream.to_string(), entry.to_string()));
+        self
+    }
+
+    /// End output with the shortest input stream (for infinite generated sources)
+    pub fn shortest(mut self) -> Self {
+        self.shortest = true;
+        self
+    }
+
+    /// Duration of the rendered output (trim duration adjusted for speed)
+    pub fn output_duration(&self) -> Option<f64> {
+        self.trim_duration.map(|d| d / self.speed.unwrap_or(1.0))
+    }
+
     /// Mute audio output
     pub fn mute(mut self) -> Self {
         self.muted = true;
@@ -218,10 +469,106 @@ impl FfmpegBuilder {
         self
     }
 
-    /// Build the argument vector
-    pub fn build_args(&self) -> Vec<String> {
+    /// Append a loudnorm filter to the audio chain (see `utils::loudness`)
+    pub fn loudnorm(mut self, filter: &str) -> Self {
+        self.loudnorm_filter = Some(filter.to_string());
+        self
+    }
+
+    /// Set output audio sample rate
+    pub fn audio_sample_rate(mut self, rate: u32) -> Self {
+        self.audio_sample_rate = Some(rate);
+        self
+    }
+
+    /// Set output audio channel count (1 = mono, 2 = stereo)
+    pub fn audio_channels(mut self, channels: u32) -> Self {
+        self.audio_channels = Some(channels);
+        self
+    }
+
+    /// Append a filter to the end of the audio chain (before loudnorm)
+    pub fn audio_filter(mut self, filter: &str) -> Self {
+        self.custom_audio_filters.push(filter.to_string());
+        self
+    }
+
+    /// Set the audio bitrate (e.g. "192k")
+    pub fn audio_bitrate(mut self, bitrate: &str) -> Self {
+        self.audio_bitrate = Some(bitrate.to_string());
+        self
+    }
+
+    /// Override the audio codec (e.g. pcm_s16le, flac, libopus)
+    pub fn audio_codec(mut self, codec: &str) -> Self {
+        self.audio_codec = Some(codec.to_string());
+        self
+    }
+
+    /// Drop the video stream from the output
+    pub fn audio_only(mut self) -> Self {
+        self.audio_only = true;
+        self
+    }
+
+    /// Drop the audio stream from the output
+    pub fn video_only(mut self) -> Self {
+        self.video_only = true;
+        self
+    }
+
+    /// Append a filter to the end of the video chain (after fades)
+    pub fn video_filter(mut self, filter: &str) -> Self {
+        self.custom_video_filters.push(filter.to_string());
+        self
+    }
+
+    /// Copy the video stream as-is and re-encode audio only
+    pub fn copy_video(mut self) -> Self {
+        self.copy_video = true;
+        self
+    }
+
+    /// Discard output (`-f null -`), used for analysis passes that report on stderr
+    pub fn analyze(mut self) -> Self {
+        self.null_output = true;
+        self
+    }
+
+    /// Add another input after the main one (input index = number of inputs added before it + 1)
+    pub fn add_input(self, path: &str) -> Self {
+        self.add_input_with_options(&[], path)
+    }
+
+    /// Add another input preceded by input options
+    pub fn add_input_with_options(mut self, options: &[&str], path: &str) -> Self {
+        self.extra_inputs.push(InputSpec {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            path: path.to_string(),
+        });
+        self
+    }
+
+    /// Set a filter graph for multi-input processing (replaces -vf/-af)
+    pub fn filter_complex(mut self, graph: &str) -> Self {
+        self.filter_complex = Some(graph.to_string());
+        self
+    }
+
+    /// Map an input stream or filter graph label into the output
+    pub fn map(mut self, spec: &str) -> Self {
+        self.maps.push(spec.to_string());
+        self
+    }
+
+    /// Build the argument vector. Fails when per-stream filters are combined with a
+    /// filter_complex graph, since FFmpeg would ignore them.
+    pub fn build_args(&self) -> FFmpegResult<Vec<String>> {
         let mut args: Vec<String> = Vec::new();
 
+        // Video filters
+        let mut filters = Vec::new();
+
         // Handle concat demuxer (special case)
         if let Some(concat_path) = &self.concat_list {
             args.extend([
@@ -253,12 +600,12 @@ impl FfmpegBuilder {
                 "-i".to_string(), self.input.as_ref().unwrap_or(&"pipe:0".to_string()).clone()
             ]);
         } else if let Some(input) = &self.input {
+            if let Some(format) = &self.input_format {
+                args.extend(["-f".to_string(), format.clone()]);
+            }
             args.extend(["-i".to_string(), input.clone()]);
         }
 
-        // Video filters
-        let mut filters = Vec::new();
-
         // Check if this is a scale+crop operation (for thumbnails)
         let is_scale_crop = self.scale_width.is_some()
             && self.scale_height.is_some()
@@ -302,10 +649,7 @@ impl FfmpegBuilder {
                     // Scale with padding to maintain aspect ratio
                     if let Some(h) = self.scale_height {
                         // Use scale with force_original_aspect_ratio and pad
-                        filters.push(format!(
-                            "scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black",
-                            w, h, w, h
-                        ));
+                        filters.push(scale_pad_filter(w, h));
                     } else {
                         // Fallback to regular scale if height not specified
                         filters.push(format!("scale={}:trunc(ih/2)*2", w));
@@ -322,14 +666,83 @@ impl FfmpegBuilder {
             }
         }
 
-        if !filters.is_empty() {
-            args.extend(["-vf".to_string(), filters.join(",")]);
+        // Captions are burned before retiming so they follow the source footage.
+        // Input seeking restarts timestamps at 0, so shift to source time for the cues.
+        if let Some(subtitles) = &self.subtitles_filter {
+            match self.trim_start.filter(|s| *s > 0.0) {
+                Some(start) => filters.push(format!("setpts=PTS+{}/TB,{},setpts=PTS-STARTPTS", start, subtitles)),
+                None => filters.push(subtitles.clone()),
+            }
+        }
+
+        // Retiming runs before fades so fade timing is relative to the retimed output
+        if self.reverse {
+            filters.push("reverse".to_string());
+        }
+        if let Some(speed) = self.speed.filter(|s| *s != 1.0) {
+            filters.push(format!("setpts=(PTS-STARTPTS)/{}", speed));
+        }
+
+        // Grading runs on the scaled frame, fades last so they reach true black
+        if let Some(lut) = &self.lut_path {
+            filters.push(format!("lut3d=file={}", escape_filter_value(lut)));
+        }
+        if let Some(eq) = self.color.as_ref().and_then(|c| c.eq_filter()) {
+            filters.push(eq);
+        }
+        for overlay in &self.text_overlays {
+            filters.push(overlay.drawtext_filter());
+        }
+        if let Some(fade_in) = self.video_fade_in.filter(|d| *d > 0.0) {
+            let fade_in = self.output_duration().map_or(fade_in, |total| fade_in.min(total));
+            filters.push(format!("fade=t=in:st=0:d={}", fade_in));
         }
+        if let (Some(fade_out), Some(total)) = (self.video_fade_out.filter(|d| *d > 0.0), self.output_duration()) {
+            let fade_out = fade_out.min(total);
+            filters.push(format!("fade=t=out:st={}:d={}", total - fade_out, fade_out));
+        }
+        filters.extend(self.custom_video_filters.iter().cloned());
+
         } // Close the else block for concat handling
 
-        // Audio filters (volume and mute)
+        // Additional inputs must come before any output options
+        for extra in &self.extra_inputs {
+            args.extend(extra.options.iter().cloned());
+            args.extend(["-i".to_string(), extra.path.clone()]);
+        }
+
+        if let Some(graph) = &self.filter_complex {
+            if !filters.is_empty() {
+                return Err(FFmpegError::InvalidOptions(format!(
+                    "video filters ({}) can't be combined with filter_complex; add them to the graph",
+                    filters.join(",")
+                )));
+            }
+            args.extend(["-filter_complex".to_string(), graph.clone()]);
+        } else if !filters.is_empty() {
+            args.extend(["-vf".to_string(), filters.join(",")]);
+        }
+        for map in &self.maps {
+            args.extend(["-map".to_string(), map.clone()]);
+        }
+
+        if self.audio_only {
+            args.push("-vn".to_string());
+        }
+        if self.video_only {
+            args.push("-an".to_string());
+        }
+
+        // Audio filters (retiming, volume and mute)
         let mut audio_filters = Vec::new();
 
+        if self.reverse {
+            audio_filters.push("areverse".to_string());
+        }
+        if let Some(speed) = self.speed {
+            audio_filters.extend(atempo_chain(speed));
+        }
+
         if self.muted {
             // Mute audio completely
             audio_filters.push("volume=0".to_string());
@@ -338,9 +751,42 @@ impl FfmpegBuilder {
             audio_filters.push(format!("volume={}", vol));
         }
 
+        if !self.muted {
+            if let Some(db) = self.gain_db {
+                if db != 0.0 {
+                    audio_filters.push(format!("volume={}dB", db));
+                }
+            }
+
+            // Fades are relative to the trimmed output, which starts at 0
+            if let Some(fade_in) = self.audio_fade_in.filter(|d| *d > 0.0) {
+                let fade_in = self.output_duration().map_or(fade_in, |total| fade_in.min(total));
+                audio_filters.push(format!("afade=t=in:st=0:d={}", fade_in));
+            }
+            if let (Some(fade_out), Some(total)) = (self.audio_fade_out.filter(|d| *d > 0.0), self.output_duration()) {
+                let fade_out = fade_out.min(total);
+                audio_filters.push(format!("afade=t=out:st={}:d={}", total - fade_out, fade_out));
+            }
+        }
+
+        audio_filters.extend(self.custom_audio_filters.iter().cloned());
+
+        // Loudness normalization runs last so it sees the final mix level
+        if let Some(loudnorm) = &self.loudnorm_filter {
+            if !self.muted {
+                audio_filters.push(loudnorm.clone());
+            }
+        }
+
         let has_audio_filters = !audio_filters.is_empty();
 
         if has_audio_filters {
+            if self.filter_complex.is_some() {
+                return Err(FFmpegError::InvalidOptions(format!(
+                    "audio filters ({}) can't be combined with filter_complex; add them to the graph",
+                    audio_filters.join(",")
+                )));
+            }
             args.extend(["-af".to_string(), audio_filters.join(",")]);
         }
 
@@ -357,6 +803,12 @@ impl FfmpegBuilder {
             } else {
                 args.extend(["-c".to_string(), "copy".to_string(), "-avoid_negative_ts".to_string(), "make_zero".to_string()]);
             }
+        } else if self.copy_video {
+            args.extend(["-c:v".to_string(), "copy".to_string()]);
+            args.extend(["-c:a".to_string(), self.audio_codec.clone().unwrap_or_else(|| "aac".to_string())]);
+            if let Some(bitrate) = &self.audio_bitrate {
+                args.extend(["-b:a".to_string(), bitrate.clone()]);
+            }
         } else {
             if let Some(codec) = &self.video_codec {
                 args.extend(["-c:v".to_string(), codec.clone()]);
@@ -378,6 +830,24 @@ impl FfmpegBuilder {
             }
         }
 
+        if let Some(rate) = self.audio_sample_rate {
+            args.extend(["-ar".to_string(), rate.to_string()]);
+        }
+        if let Some(channels) = self.audio_channels {
+            args.extend(["-ac".to_string(), channels.to_string()]);
+        }
+
+        if let Some(codec) = &self.subtitle_codec {
+            args.extend(["-c:s".to_string(), codec.clone()]);
+        }
+        for (stream, entry) in &self.stream_metadata {
+            args.extend([format!("-metadata:{}", stream), entry.clone()]);
+        }
+
+        if self.shortest {
+            args.push("-shortest".to_string());
+        }
+
         // Thumbnail-specific parameters
         if self.thumbnail_time.is_some() {
             args.extend(["-vframes".to_string(), "1".to_string()]);
@@ -388,11 +858,15 @@ impl FfmpegBuilder {
             args.extend(["-progress".to_string(), "pipe:2".to_string()]);
         }
 
-        if let Some(output) = &self.output {
+        args.extend(self.output_options.iter().cloned());
+
+        if self.null_output {
+            args.extend(["-f".to_string(), "null".to_string(), "-".to_string()]);
+        } else if let Some(output) = &self.output {
             args.extend(["-y".to_string(), output.clone()]);
         }
 
-        args
+        Ok(args)
     }
 
     /// Get platform-specific binary name with extension
@@ -517,7 +991,7 @@ impl FfmpegBuilder {
 
     /// Execute the FFmpeg command synchronously (for short operations)
     pub fn run_sync(&self) -> FFmpegResult<String> {
-        let args = self.build_args();
+        let args = self.build_args()?;
         let output = self.execute_command(&args)?;
 
         if output.status.success() {
@@ -529,6 +1003,21 @@ impl FfmpegBuilder {
         }
     }
 
+    /// Execute an analysis command and return its stderr, where FFmpeg filters
+    /// such as loudnorm and silencedetect print their reports
+    pub fn run_analysis(&self) -> FFmpegResult<String> {
+        let args = self.build_args()?;
+        let output = self.execute_command(&args)?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stderr).to_string())
+        } else {
+            Err(FFmpegError::ExecutionFailed(
+                String::from_utf8_lossy(&output.stderr).to_string()
+            ))
+        }
+    }
+
     /// Execute command with sidecar fallback and optional timeout
     fn execute_command(&self, args: &[String]) -> FFmpegResult<std::process::Output> {
         // Require app_handle for sidecar resolution
@@ -618,7 +1107,7 @@ impl FfmpegBuilder {
 
     /// Execute the FFmpeg command asynchronously
     pub fn run(&self, app_handle: &tauri::AppHandle) -> FFmpegResult<String> {
-        let args = self.build_args();
+        let args = self.build_args()?;
 
         // Try bundled binary first
         let binary_path = match Self::resolve_sidecar_binary(app_handle, "ffmpeg") {
@@ -655,7 +1144,7 @@ impl FfmpegBuilder {
     /// - progress_offset: starting percentage (0-100)
     /// - progress_range: how much of the total progress this phase represents (e.g., 30 means this phase is 30% of total)
     pub async fn run_with_progress(&self, app_handle: &tauri::AppHandle, duration: Option<f64>, progress_offset: u32, progress_range: u32) -> FFmpegResult<String> {
-        let args = self.build_args();
+        let args = self.build_args()?;
 
         // Resolve bundled binary
         let binary_path = match Self::resolve_sidecar_binary(app_handle, "ffmpeg") {
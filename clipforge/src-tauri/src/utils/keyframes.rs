@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+/// Keyframe positions of a clip's video stream, cached next to its thumbnail
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyframeIndex {
+    pub source_path: String,
+    pub source_size: u64,      // Source file size when indexed
+    pub source_modified: u64,  // Source mtime (seconds since epoch) when indexed
+    pub duration: f64,         // End of the last video packet
+    pub packet_count: usize,
+    pub keyframes: Vec<f64>,   // Sorted keyframe timestamps in seconds
+}
+
+/// Keyframes on either side of a timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct KeyframeNeighbors {
+    pub before: Option<f64>,   // Last keyframe at or before the time
+    pub after: Option<f64>,    // First keyframe at or after the time
+    pub nearest: Option<f64>,
+}
+
+/// Size and mtime of a file, used to detect a stale index
+pub fn source_stamp(path: &Path) -> Result<(u64, u64), String> {
+    let metadata = fs::metadata(path)
+        .map_err(|e| format!("Failed to read metadata of {}: {}", path.display(), e))?;
+    let modified = metadata.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs());
+    Ok((metadata.len(), modified))
+}
+
+/// Cache file name for a source: its file name plus a hash of the full path,
+/// so same-named files in different folders get separate indexes
+pub fn cache_file_name(source_path: &str) -> String {
+    // FNV-1a, stable across builds unlike std's DefaultHasher
+    let hash = source_path.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    let name = Path::new(source_path).file_name().and_then(|n| n.to_str()).unwrap_or("source");
+    format!("{}_{:016x}_keyframes.json", name, hash)
+}
+
+impl KeyframeIndex {
+    /// Build an index from
+    /// `ffprobe -select_streams v:0 -show_entries packet=pts_time,duration_time,flags -of csv=p=0`
+    /// (reading packets needs no decoding, keyframe packets carry the K flag)
+    pub fn from_packets(source_path: &str, stamp: (u64, u64), stdout: &str) -> Self {
+        let mut keyframes = Vec::new();
+        let mut packet_count = 0;
+        let mut duration: f64 = 0.0;
+
+        for line in stdout.lines() {
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            let pts = match fields.first().and_then(|f| f.parse::<f64>().ok()) {
+                Some(pts) => pts,
+                None => continue,
+            };
+            packet_count += 1;
+            let packet_duration = fields.get(1).and_then(|f| f.parse::<f64>().ok()).unwrap_or(0.0);
+            duration = duration.max(pts + packet_duration);
+            if fields.get(2).is_some_and(|flags| flags.contains('K')) {
+                keyframes.push(pts);
+            }
+        }
+
+        keyframes.sort_by(|a, b| a.total_cmp(b));
+        keyframes.dedup();
+
+        Self {
+            source_path: source_path.to_string(),
+            source_size: stamp.0,
+            source_modified: stamp.1,
+            duration,
+            packet_count,
+            keyframes,
+        }
+    }
+
+    /// Whether the index still describes `source_path` with the given stamp
+    pub fn is_fresh(&self, source_path: &str, stamp: (u64, u64)) -> bool {
+        self.source_path == source_path && (self.source_size, self.source_modified) == stamp
+    }
+
+    /// Load a cached index, or None when missing, unreadable or stale
+    pub fn read_cache(cache_path: &Path, source_path: &str, stamp: (u64, u64)) -> Option<Self> {
+        let content = fs::read_to_string(cache_path).ok()?;
+        let index: Self = serde_json::from_str(&content).ok()?;
+        index.is_fresh(source_path, stamp).then_some(index)
+    }
+
+    /// Save the index as JSON
+    pub fn write_cache(&self, cache_path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize keyframe index: {}", e))?;
+        fs::write(cache_path, json)
+            .map_err(|e| format!("Failed to write keyframe index: {}", e))
+    }
+
+    /// Last keyframe at or before `time`
+    pub fn before(&self, time: f64) -> Option<f64> {
+        let i = self.keyframes.partition_point(|k| *k <= time);
+        i.checked_sub(1).map(|i| self.keyframes[i])
+    }
+
+    /// First keyframe at or after `time`
+    pub fn after(&self, time: f64) -> Option<f64> {
+        let i = self.keyframes.partition_point(|k| *k < time);
+        self.keyframes.get(i).copied()
+    }
+
+    /// Keyframes around `time` and the closest of the two
+    pub fn neighbors(&self, time: f64) -> KeyframeNeighbors {
+        let before = self.before(time);
+        let after = self.after(time);
+        let nearest = match (before, after) {
+            (Some(b), Some(a)) => Some(if time - b <= a - time { b } else { a }),
+            (b, a) => b.or(a),
+        };
+        KeyframeNeighbors { before, after, nearest }
+    }
+}
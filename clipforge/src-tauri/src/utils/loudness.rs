@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+/// EBU R128 loudness values reported by the loudnorm filter's measurement pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessMeasurement {
+    pub integrated: f64,    // Integrated loudness (LUFS)
+    pub true_peak: f64,     // True peak (dBTP)
+    pub lra: f64,           // Loudness range (LU)
+    pub threshold: f64,     // Gating threshold (LUFS)
+    pub target_offset: f64, // Offset gain to hit the target (LU)
+}
+
+impl LoudnessMeasurement {
+    /// Silent or near-silent input measures as -inf and cannot be normalized
+    pub fn is_measurable(&self) -> bool {
+        self.integrated.is_finite() && self.true_peak.is_finite() && self.threshold.is_finite()
+    }
+}
+
+/// Normalization target for two-pass loudnorm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessTarget {
+    pub integrated: f64, // Target integrated loudness (LUFS), e.g. -14 for web, -23 for broadcast
+    #[serde(default = "default_true_peak")]
+    pub true_peak: f64,  // Maximum true peak (dBTP)
+    #[serde(default = "default_lra")]
+    pub lra: f64,        // Target loudness range (LU)
+}
+
+fn default_true_peak() -> f64 {
+    -1.0
+}
+
+fn default_lra() -> f64 {
+    11.0
+}
+
+impl LoudnessTarget {
+    /// Streaming/web delivery (-14 LUFS)
+    pub fn web() -> Self {
+        Self { integrated: -14.0, true_peak: -1.0, lra: 11.0 }
+    }
+
+    /// EBU R128 broadcast delivery (-23 LUFS)
+    pub fn broadcast() -> Self {
+        Self { integrated: -23.0, true_peak: -1.0, lra: 7.0 }
+    }
+
+    /// Clamp values to the ranges accepted by the loudnorm filter
+    pub fn clamped(&self) -> Self {
+        Self {
+            integrated: self.integrated.clamp(-70.0, -5.0),
+            true_peak: self.true_peak.clamp(-9.0, 0.0),
+            lra: self.lra.clamp(1.0, 50.0),
+        }
+    }
+
+    /// First pass: measure input loudness and print it as JSON on stderr
+    pub fn measure_filter(&self) -> String {
+        let t = self.clamped();
+        format!("loudnorm=I={}:TP={}:LRA={}:print_format=json", t.integrated, t.true_peak, t.lra)
+    }
+
+    /// Second pass: linear normalization using values from the first pass
+    pub fn normalize_filter(&self, measured: &LoudnessMeasurement) -> String {
+        let t = self.clamped();
+        format!(
+            "loudnorm=I={}:TP={}:LRA={}:measured_I={:.2}:measured_TP={:.2}:measured_LRA={:.2}:measured_thresh={:.2}:offset={:.2}:linear=true:print_format=summary",
+            t.integrated, t.true_peak, t.lra,
+            measured.integrated, measured.true_peak, measured.lra, measured.threshold, measured.target_offset
+        )
+    }
+}
+
+/// Parse the JSON block printed by `loudnorm=print_format=json` from FFmpeg stderr
+pub fn parse_loudnorm_output(stderr: &str) -> Option<LoudnessMeasurement> {
+    // The JSON block is the last {...} in the log
+    let start = stderr.rfind('{')?;
+    let end = stderr[start..].find('}')? + start;
+    let json: serde_json::Value = serde_json::from_str(&stderr[start..=end]).ok()?;
+
+    let field = |name: &str| -> Option<f64> {
+        json[name].as_str().and_then(|s| s.trim().parse::<f64>().ok())
+    };
+
+    Some(LoudnessMeasurement {
+        integrated: field("input_i")?,
+        true_peak: field("input_tp")?,
+        lra: field("input_lra")?,
+        threshold: field("input_thresh")?,
+        target_offset: field("target_offset").unwrap_or(0.0),
+    })
+}
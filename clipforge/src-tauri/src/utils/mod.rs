@@ -1 +1,16 @@
-pub mod ffmpeg;
\ No newline at end of file
+pub mod ffmpeg;
+pub mod loudness;
+pub mod audio_mix;
+pub mod transitions;
+pub mod text_overlay;
+pub mod overlay;
+pub mod composition;
+pub mod subtitles;
+pub mod transcription;
+pub mod audio_export;
+pub mod detection;
+pub mod trim;
+pub mod keyframes;
+pub mod clip;
+pub mod timeline;
+pub mod workspace;
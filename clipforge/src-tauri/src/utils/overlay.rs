@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use super::text_overlay::enable_expression;
+
+/// Corner/center anchor for an overlaid image or video
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+    Center,
+}
+
+impl Anchor {
+    /// overlay filter x/y expressions (W/H = main frame, w/h = overlay) for a margin in pixels
+    pub fn overlay_position(&self, margin: u32) -> (String, String) {
+        let m = margin;
+        match self {
+            Anchor::TopLeft => (m.to_string(), m.to_string()),
+            Anchor::TopRight => (format!("W-w-{}", m), m.to_string()),
+            Anchor::BottomLeft => (m.to_string(), format!("H-h-{}", m)),
+            Anchor::BottomRight => (format!("W-w-{}", m), format!("H-h-{}", m)),
+            Anchor::Center => ("(W-w)/2".to_string(), "(H-h)/2".to_string()),
+        }
+    }
+}
+
+/// Logo/image watermark burned over the program
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Watermark {
+    pub path: String,  // PNG (transparency is preserved)
+    #[serde(default)]
+    pub anchor: Anchor,
+    #[serde(default = "default_margin")]
+    pub margin: u32,   // Distance from the anchored edges in pixels
+    #[serde(default = "default_scale")]
+    pub scale: f64,    // Watermark width as a fraction of the output width
+    #[serde(default = "default_opacity")]
+    pub opacity: f64,  // 0.0-1.0
+    pub start: Option<f64>,  // Visible from (seconds on the program timeline)
+    pub end: Option<f64>,    // Visible until
+}
+
+fn default_margin() -> u32 {
+    24
+}
+
+fn default_scale() -> f64 {
+    0.15
+}
+
+fn default_opacity() -> f64 {
+    1.0
+}
+
+/// Round a pixel dimension down to an even number (required by yuv420p), minimum 2
+pub fn even_dimension(value: f64) -> u32 {
+    ((value.max(2.0) as u32) / 2) * 2
+}
+
+impl Watermark {
+    /// Build graph chains overlaying input `input_index` onto `video_in`, labelled `out`
+    pub fn overlay_graph(&self, input_index: usize, video_in: &str, out: &str, output_width: u32) -> String {
+        let width = even_dimension(output_width as f64 * self.scale.clamp(0.01, 1.0));
+        let opacity = self.opacity.clamp(0.0, 1.0);
+
+        let mut logo = format!("[{}:v]format=rgba,scale={}:-1", input_index, width);
+        if opacity < 1.0 {
+            logo.push_str(&format!(",colorchannelmixer=aa={}", opacity));
+        }
+
+        let (x, y) = self.anchor.overlay_position(self.margin);
+        let mut overlay = format!("{}[wm]overlay=x={}:y={}", video_in, x, y);
+        if let Some(enable) = enable_expression(self.start, self.end) {
+            overlay.push_str(&format!(":enable='{}'", enable));
+        }
+
+        format!("{}[wm];{}{}", logo, overlay, out)
+    }
+}
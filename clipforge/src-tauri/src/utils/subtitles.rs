@@ -0,0 +1,678 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::ffmpeg::escape_filter_value;
+
+/// Caption file formats FFmpeg's subtitles filter is given
+pub const CAPTION_EXTENSIONS: [&str; 4] = ["vtt", "srt", "ass", "ssa"];
+
+/// Whether a caption file has a supported extension (VTT, SRT or ASS/SSA)
+pub fn is_supported_caption_file(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| CAPTION_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Vertical placement of burned-in captions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionPosition {
+    #[default]
+    Bottom,
+    Middle,
+    Top,
+}
+
+impl CaptionPosition {
+    /// ASS numpad alignment (centered horizontally)
+    pub fn ass_alignment(&self) -> u32 {
+        match self {
+            CaptionPosition::Bottom => 2,
+            CaptionPosition::Middle => 5,
+            CaptionPosition::Top => 8,
+        }
+    }
+}
+
+/// Styling for captions burned into the video
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptionStyle {
+    pub font_name: Option<String>,  // Installed font family (libass default when None)
+    #[serde(default = "default_font_size")]
+    pub font_size: u32,             // In libass units (relative to a 288px-tall frame)
+    #[serde(default = "default_color")]
+    pub color: String,              // "#RRGGBB"
+    #[serde(default = "default_outline_color")]
+    pub outline_color: String,      // "#RRGGBB"
+    #[serde(default = "default_outline")]
+    pub outline: f64,               // Outline thickness (0 for none)
+    #[serde(default)]
+    pub bold: bool,
+    #[serde(default)]
+    pub position: CaptionPosition,
+    #[serde(default = "default_margin")]
+    pub margin: u32,                // Distance from the top/bottom edge
+}
+
+fn default_font_size() -> u32 {
+    18
+}
+
+fn default_color() -> String {
+    "#FFFFFF".to_string()
+}
+
+fn default_outline_color() -> String {
+    "#000000".to_string()
+}
+
+fn default_outline() -> f64 {
+    1.5
+}
+
+fn default_margin() -> u32 {
+    20
+}
+
+impl Default for CaptionStyle {
+    fn default() -> Self {
+        Self {
+            font_name: None,
+            font_size: default_font_size(),
+            color: default_color(),
+            outline_color: default_outline_color(),
+            outline: default_outline(),
+            bold: false,
+            position: CaptionPosition::default(),
+            margin: default_margin(),
+        }
+    }
+}
+
+/// Convert "#RRGGBB" to an ASS color (&HAABBGGRR, opaque)
+pub fn ass_color(color: &str) -> Option<String> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_uppercase();
+    Some(format!("&H00{}{}{}", &hex[4..6], &hex[2..4], &hex[0..2]))
+}
+
+impl CaptionStyle {
+    /// Check colors and font name before rendering
+    pub fn validate(&self) -> Result<(), String> {
+        for color in [&self.color, &self.outline_color] {
+            if ass_color(color).is_none() {
+                return Err(format!("Invalid caption color: {}. Use #RRGGBB.", color));
+            }
+        }
+        if let Some(font) = &self.font_name {
+            if font.contains(',') {
+                return Err(format!("Invalid caption font name: {}", font));
+            }
+        }
+        Ok(())
+    }
+
+    /// ASS style overrides for the subtitles filter's force_style option
+    pub fn force_style(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some(font) = &self.font_name {
+            fields.push(format!("FontName={}", font));
+        }
+        fields.push(format!("FontSize={}", self.font_size));
+        if let Some(color) = ass_color(&self.color) {
+            fields.push(format!("PrimaryColour={}", color));
+        }
+        if let Some(color) = ass_color(&self.outline_color) {
+            fields.push(format!("OutlineColour={}", color));
+        }
+        fields.push("BorderStyle=1".to_string());
+        fields.push(format!("Outline={}", self.outline.clamp(0.0, 10.0)));
+        fields.push(format!("Bold={}", if self.bold { -1 } else { 0 }));
+        fields.push(format!("Alignment={}", self.position.ass_alignment()));
+        fields.push(format!("MarginV={}", self.margin));
+        fields.join(",")
+    }
+
+    /// subtitles filter burning `caption_path` (VTT or SRT) with this style
+    pub fn subtitles_filter(&self, caption_path: &str) -> String {
+        format!(
+            "subtitles=filename={}:force_style={}",
+            escape_filter_value(caption_path),
+            escape_filter_value(&self.force_style())
+        )
+    }
+}
+
+/// A single caption cue (times in seconds)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cue {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,  // May span multiple lines
+}
+
+/// Caption file format (also used for the sidecar written next to an export)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionFormat {
+    #[default]
+    Vtt,
+    Srt,
+    Ass,
+}
+
+impl CaptionFormat {
+    /// Format matching a file's extension
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path).extension().and_then(|e| e.to_str())?.to_lowercase();
+        match extension.as_str() {
+            "vtt" => Some(CaptionFormat::Vtt),
+            "srt" => Some(CaptionFormat::Srt),
+            "ass" | "ssa" => Some(CaptionFormat::Ass),
+            _ => None,
+        }
+    }
+
+    /// File extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CaptionFormat::Vtt => "vtt",
+            CaptionFormat::Srt => "srt",
+            CaptionFormat::Ass => "ass",
+        }
+    }
+
+    /// Parse content in this format
+    pub fn parse(&self, content: &str) -> Result<Vec<Cue>, String> {
+        match self {
+            CaptionFormat::Vtt => parse_vtt(content),
+            CaptionFormat::Srt => parse_srt(content),
+            CaptionFormat::Ass => parse_ass(content),
+        }
+    }
+
+    /// Serialize cues in this format
+    pub fn write(&self, cues: &[Cue]) -> String {
+        match self {
+            CaptionFormat::Vtt => write_vtt(cues),
+            CaptionFormat::Srt => write_srt(cues),
+            CaptionFormat::Ass => write_ass(cues),
+        }
+    }
+}
+
+/// Parse a VTT/SRT timestamp ("hh:mm:ss.mmm", "mm:ss.mmm" or "hh:mm:ss,mmm") into seconds
+pub fn parse_timestamp(value: &str) -> Option<f64> {
+    let value = value.trim().replace(',', ".");
+    let parts: Vec<&str> = value.split(':').collect();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<u64>().ok()?, m.parse::<u64>().ok()?, s.parse::<f64>().ok()?),
+        [m, s] => (0, m.parse::<u64>().ok()?, s.parse::<f64>().ok()?),
+        _ => return None,
+    };
+    if minutes >= 60 || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+    Some((hours * 3600 + minutes * 60) as f64 + seconds)
+}
+
+/// Format seconds as "hh:mm:ss" plus milliseconds after `separator` ('.' for VTT, ',' for SRT)
+pub fn format_timestamp(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        separator,
+        total_ms % 1000
+    )
+}
+
+// Parse a "start --> end [settings]" timing line
+fn parse_timing(line: &str) -> Option<(f64, f64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start)?, parse_timestamp(end)?))
+}
+
+// Shared block parser: VTT and SRT are both blank-line separated blocks with an
+// optional identifier line, a timing line and the cue text
+fn parse_blocks(content: &str) -> Vec<Cue> {
+    let normalized = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+
+    for block in normalized.split("\n\n") {
+        let lines: Vec<&str> = block.lines().collect();
+        let Some(timing_index) = lines.iter().position(|l| l.contains("-->")) else {
+            continue;  // Header, NOTE, STYLE or REGION block
+        };
+        let Some((start, end)) = parse_timing(lines[timing_index]) else {
+            continue;
+        };
+        let text = lines[timing_index + 1..].join("\n");
+        if end > start && !text.trim().is_empty() {
+            cues.push(Cue { start, end, text });
+        }
+    }
+
+    cues
+}
+
+/// Parse WebVTT content into cues
+pub fn parse_vtt(content: &str) -> Result<Vec<Cue>, String> {
+    if !content.trim_start_matches('\u{feff}').starts_with("WEBVTT") {
+        return Err("Not a WebVTT file (missing WEBVTT header)".to_string());
+    }
+    Ok(parse_blocks(content))
+}
+
+/// Parse SubRip content into cues
+pub fn parse_srt(content: &str) -> Result<Vec<Cue>, String> {
+    let cues = parse_blocks(content);
+    if cues.is_empty() && !content.trim().is_empty() {
+        return Err("No valid SRT cues found".to_string());
+    }
+    Ok(cues)
+}
+
+// Format for a caption path, or an error naming the supported formats
+fn format_for_path(path: &str) -> Result<CaptionFormat, String> {
+    CaptionFormat::from_path(path)
+        .ok_or_else(|| format!("Unsupported caption format: {}. Use VTT, SRT or ASS.", path))
+}
+
+/// Read and parse a VTT, SRT or ASS file, choosing the parser by extension
+pub fn read_caption_file(path: &str) -> Result<Vec<Cue>, String> {
+    let format = format_for_path(path)?;
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read caption file {}: {}", path, e))?;
+    format.parse(&content)
+}
+
+/// Write cues to a VTT, SRT or ASS file, choosing the writer by extension
+pub fn write_caption_file(path: &str, cues: &[Cue]) -> Result<(), String> {
+    let format = format_for_path(path)?;
+    std::fs::write(path, format.write(cues))
+        .map_err(|e| format!("Failed to write caption file {}: {}", path, e))
+}
+
+/// Write cues edited from `source_path` to a new file. Only timing and text are
+/// kept, so the source (ASS styles, VTT cue settings and notes) is never overwritten.
+pub fn write_edited_captions(source_path: &str, output_path: &str, cues: &[Cue]) -> Result<(), String> {
+    let same_file = Path::new(source_path) == Path::new(output_path)
+        || matches!(
+            (std::fs::canonicalize(source_path), std::fs::canonicalize(output_path)),
+            (Ok(source), Ok(output)) if source == output
+        );
+    if same_file {
+        return Err(format!("Refusing to overwrite the source captions {}; choose a new output path", source_path));
+    }
+    write_caption_file(output_path, cues)
+}
+
+// ASS override blocks like {\i1} are styling only; \{ and \} are literal braces
+fn strip_ass_overrides(text: &str) -> String {
+    let mut out = String::new();
+    let mut depth = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if depth == 0 && matches!(chars.peek(), Some('{') | Some('}')) => {
+                out.extend(chars.next());
+            }
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Parse Advanced SubStation Alpha content into cues (styling is dropped)
+pub fn parse_ass(content: &str) -> Result<Vec<Cue>, String> {
+    let mut in_events = false;
+    let mut fields: Vec<String> = Vec::new();
+    let mut cues = Vec::new();
+
+    for line in content.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[events]");
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+
+        if let Some(format) = line.strip_prefix("Format:") {
+            fields = format.split(',').map(|f| f.trim().to_lowercase()).collect();
+        } else if let Some(dialogue) = line.strip_prefix("Dialogue:") {
+            let position = |name: &str| fields.iter().position(|f| f == name);
+            let (Some(start_index), Some(end_index), Some(text_index)) = (position("start"), position("end"), position("text")) else {
+                return Err("ASS Dialogue before a valid Format line".to_string());
+            };
+
+            // Text is the last field and may itself contain commas
+            let values: Vec<&str> = dialogue.splitn(fields.len(), ',').map(|v| v.trim()).collect();
+            if values.len() != fields.len() {
+                continue;
+            }
+            let (Some(start), Some(end)) = (parse_timestamp(values[start_index]), parse_timestamp(values[end_index])) else {
+                continue;
+            };
+            let text = strip_ass_overrides(values[text_index])
+                .replace("\\N", "\n")
+                .replace("\\n", "\n")
+                .replace("\\h", " ");
+            if end > start && !text.trim().is_empty() {
+                cues.push(Cue { start, end, text });
+            }
+        }
+    }
+
+    if cues.is_empty() && !fields.iter().any(|f| f == "text") {
+        return Err("Not an ASS file (missing [Events] section)".to_string());
+    }
+    cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+    Ok(cues)
+}
+
+// ASS timestamps are h:mm:ss.cc (centiseconds)
+fn format_ass_timestamp(seconds: f64) -> String {
+    let total_cs = (seconds.max(0.0) * 100.0).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        total_cs / 360_000,
+        (total_cs / 6000) % 60,
+        (total_cs / 100) % 60,
+        total_cs % 100
+    )
+}
+
+/// Serialize cues as ASS with a single default style
+pub fn write_ass(cues: &[Cue]) -> String {
+    let mut out = String::from(
+        "[Script Info]\n\
+         ScriptType: v4.00+\n\
+         PlayResX: 384\n\
+         PlayResY: 288\n\
+         \n\
+         [V4+ Styles]\n\
+         Format: Name, Fontname, Fontsize, PrimaryColour, OutlineColour, BackColour, Bold, Italic, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+         Style: Default,Arial,18,&H00FFFFFF,&H00000000,&H00000000,0,0,1,1.5,0,2,10,10,20,1\n\
+         \n\
+         [Events]\n\
+         Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+    );
+    for cue in cues {
+        out.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+            format_ass_timestamp(cue.start),
+            format_ass_timestamp(cue.end),
+            // Braces would start an override block
+            strip_timestamp_tags(&cue.text).replace('{', "\\{").replace('}', "\\}").replace('\n', "\\N")
+        ));
+    }
+    out
+}
+
+/// Serialize cues as WebVTT
+pub fn write_vtt(cues: &[Cue]) -> String {
+    let mut out = String::from("WEBVTT\n");
+    for cue in cues {
+        out.push_str(&format!(
+            "\n{} --> {}\n{}\n",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.'),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// Serialize cues as SubRip
+pub fn write_srt(cues: &[Cue]) -> String {
+    let mut blocks = Vec::new();
+    for (i, cue) in cues.iter().enumerate() {
+        blocks.push(format!(
+            "{}\n{} --> {}\n{}\n",
+            i + 1,
+            format_timestamp(cue.start, ','),
+            format_timestamp(cue.end, ','),
+            strip_timestamp_tags(&cue.text)
+        ));
+    }
+    blocks.join("\n")
+}
+
+/// Remove WebVTT word timestamp tags (`<00:00:01.500>`); they are only valid in
+/// VTT and would be wrong once cues are moved
+pub fn strip_timestamp_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('<') {
+        out.push_str(&rest[..open]);
+        let tag = &rest[open..];
+        match tag.find('>') {
+            Some(close) if parse_timestamp(&tag[1..close]).is_some() => rest = &tag[close + 1..],
+            _ => {
+                out.push('<');
+                rest = &tag[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// How a clip's source captions map onto the export timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CueTiming {
+    pub trim_start: f64,
+    pub trim_end: f64,
+    pub speed: f64,
+    pub reverse: bool,
+    pub offset: f64,  // Clip start on the program timeline
+}
+
+/// Clip cues to a clip's trim range and move them onto the program timeline.
+/// Cues overlapping the trim edges are cut; cues outside it are dropped.
+pub fn retime_cues(cues: &[Cue], timing: &CueTiming) -> Vec<Cue> {
+    let speed = if timing.speed > 0.0 { timing.speed } else { 1.0 };
+
+    cues.iter()
+        .filter_map(|cue| {
+            let start = cue.start.max(timing.trim_start);
+            let end = cue.end.min(timing.trim_end);
+            if end <= start {
+                return None;
+            }
+
+            // Position within the clip, in source seconds, before retiming
+            let (from, to) = if timing.reverse {
+                (timing.trim_end - end, timing.trim_end - start)
+            } else {
+                (start - timing.trim_start, end - timing.trim_start)
+            };
+
+            Some(Cue {
+                start: timing.offset + from / speed,
+                end: timing.offset + to / speed,
+                text: strip_timestamp_tags(&cue.text),
+            })
+        })
+        .collect()
+}
+
+/// Merge per-clip cues into one track ordered by start time, dropping anything past `duration`
+pub fn merge_cues(tracks: Vec<Vec<Cue>>, duration: f64) -> Vec<Cue> {
+    let mut merged: Vec<Cue> = tracks.into_iter()
+        .flatten()
+        .filter(|cue| cue.start < duration)
+        .map(|mut cue| {
+            cue.end = cue.end.min(duration);
+            cue
+        })
+        .collect();
+    merged.sort_by(|a, b| a.start.total_cmp(&b.start));
+    merged
+}
+
+/// Subtitle codec used when embedding into Matroska outputs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MkvSubtitleCodec {
+    #[default]
+    Srt,
+    Ass,
+}
+
+/// Captions muxed into the output container as a selectable subtitle stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedCaptions {
+    #[serde(default = "default_language")]
+    pub language: String,       // ISO 639-2 code, e.g. "eng"
+    pub title: Option<String>,  // Track name shown by players
+    #[serde(default)]
+    pub mkv_codec: MkvSubtitleCodec,
+}
+
+fn default_language() -> String {
+    "eng".to_string()
+}
+
+impl EmbeddedCaptions {
+    /// Check the language tag is a three-letter ISO 639-2 code
+    pub fn validate(&self) -> Result<(), String> {
+        if self.language.len() != 3 || !self.language.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(format!("Invalid caption language: {}. Use a three-letter ISO 639-2 code like \"eng\".", self.language));
+        }
+        Ok(())
+    }
+
+    /// Subtitle codec for the output container, chosen by extension
+    pub fn codec_for(&self, output_path: &str) -> Result<&'static str, String> {
+        let extension = Path::new(output_path).extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase());
+        match extension.as_deref() {
+            Some("mp4") | Some("m4v") | Some("mov") => Ok("mov_text"),
+            Some("mkv") => Ok(match self.mkv_codec {
+                MkvSubtitleCodec::Srt => "srt",
+                MkvSubtitleCodec::Ass => "ass",
+            }),
+            // Exports are H.264/AAC, which WebM can't hold
+            _ => Err(format!("Embedded captions are not supported for {}. Use MP4, MOV or MKV.", output_path)),
+        }
+    }
+
+    /// Stream metadata entries for the subtitle track
+    pub fn metadata(&self) -> Vec<String> {
+        let mut entries = vec![format!("language={}", self.language)];
+        if let Some(title) = &self.title {
+            entries.push(format!("title={}", title));
+        }
+        entries
+    }
+}
+
+/// Move every cue by `offset` seconds. Cues pushed entirely before 0 are dropped,
+/// cues straddling 0 are cut to start at 0.
+pub fn shift_cues(cues: &[Cue], offset: f64) -> Vec<Cue> {
+    cues.iter()
+        .filter(|cue| cue.end + offset > 0.0)
+        .map(|cue| Cue {
+            start: (cue.start + offset).max(0.0),
+            end: cue.end + offset,
+            text: strip_timestamp_tags(&cue.text),
+        })
+        .collect()
+}
+
+/// Split cue `index` in two at time `at`. Words are divided in proportion to the
+/// time on each side (a single word is kept on both halves).
+pub fn split_cue(cues: &[Cue], index: usize, at: f64) -> Result<Vec<Cue>, String> {
+    let cue = cues.get(index).ok_or_else(|| format!("Cue {} does not exist", index))?;
+    if at <= cue.start || at >= cue.end {
+        return Err(format!(
+            "Split point {:.3}s is outside cue {} ({:.3}s-{:.3}s)",
+            at, index, cue.start, cue.end
+        ));
+    }
+
+    let words: Vec<&str> = cue.text.split_whitespace().collect();
+    let (first_text, second_text) = if words.len() < 2 {
+        (cue.text.clone(), cue.text.clone())
+    } else {
+        let ratio = (at - cue.start) / (cue.end - cue.start);
+        let split = ((words.len() as f64 * ratio).round() as usize).clamp(1, words.len() - 1);
+        (words[..split].join(" "), words[split..].join(" "))
+    };
+
+    let mut result = cues.to_vec();
+    result[index] = Cue { start: cue.start, end: at, text: first_text };
+    result.insert(index + 1, Cue { start: at, end: cue.end, text: second_text });
+    Ok(result)
+}
+
+/// Merge cue `index` with the cue that follows it
+pub fn merge_with_next(cues: &[Cue], index: usize) -> Result<Vec<Cue>, String> {
+    if index + 1 >= cues.len() {
+        return Err(format!("Cue {} has no following cue to merge with", index));
+    }
+
+    let (first, second) = (&cues[index], &cues[index + 1]);
+    let merged = Cue {
+        start: first.start.min(second.start),
+        end: first.end.max(second.end),
+        text: format!("{}\n{}", first.text, second.text),
+    };
+
+    let mut result = cues.to_vec();
+    result[index] = merged;
+    result.remove(index + 1);
+    Ok(result)
+}
+
+/// Greedily wrap text to at most `max_chars` per line (longer words get their own line)
+pub fn wrap_text(text: &str, max_chars: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        if current.is_empty() {
+            current = word.to_string();
+        } else if current.chars().count() + 1 + word.chars().count() <= max_chars {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current = word.to_string();
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines.join("\n")
+}
+
+/// Re-wrap every cue's text to at most `max_chars` characters per line
+pub fn rewrap_cues(cues: &[Cue], max_chars: usize) -> Result<Vec<Cue>, String> {
+    if max_chars == 0 {
+        return Err("Max characters per line must be at least 1".to_string());
+    }
+    Ok(cues.iter()
+        .map(|cue| Cue {
+            start: cue.start,
+            end: cue.end,
+            text: wrap_text(&cue.text, max_chars),
+        })
+        .collect())
+}
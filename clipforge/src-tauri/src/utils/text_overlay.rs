@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use super::ffmpeg::escape_filter_value;
+
+/// Where a text layer is anchored in the frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TextPosition {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    Center,
+    BottomLeft,
+    #[default]
+    BottomCenter,
+    BottomRight,
+    LowerThird,  // Left-aligned, a third of the way up from the bottom
+    Custom,      // Uses the overlay's x/y expressions
+}
+
+/// A text layer rendered with drawtext
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextOverlay {
+    pub text: String,
+    pub font_file: Option<String>,  // Path to .ttf/.otf (system default font when None)
+    #[serde(default = "default_font_size")]
+    pub font_size: u32,
+    #[serde(default = "default_color")]
+    pub color: String,  // FFmpeg color, e.g. "white" or "#FFCC00@0.9"
+    pub box_color: Option<String>,  // Background box color (no box when None)
+    #[serde(default = "default_box_border")]
+    pub box_border: u32,
+    #[serde(default)]
+    pub position: TextPosition,
+    pub x: Option<String>,  // Custom x expression (pixels or drawtext expression)
+    pub y: Option<String>,  // Custom y expression
+    #[serde(default = "default_margin")]
+    pub margin: u32,
+    pub start: Option<f64>,  // Visible from (seconds on the program timeline)
+    pub end: Option<f64>,    // Visible until
+}
+
+fn default_font_size() -> u32 {
+    48
+}
+
+fn default_color() -> String {
+    "white".to_string()
+}
+
+fn default_box_border() -> u32 {
+    12
+}
+
+fn default_margin() -> u32 {
+    40
+}
+
+/// Escape text for drawtext's `text` option (quoted, so commas are safe)
+pub fn escape_drawtext_text(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace(':', "\\:")
+        .replace('\'', "'\\\\\\''");
+    format!("'{}'", escaped)
+}
+
+/// Check an FFmpeg color: a name ("white") or #RRGGBB[AA]/0xRRGGBB[AA], with an optional @alpha
+pub fn is_valid_color(value: &str) -> bool {
+    let (color, alpha) = match value.split_once('@') {
+        Some((color, alpha)) => (color, Some(alpha)),
+        None => (value, None),
+    };
+    let hex = color.strip_prefix('#').or_else(|| color.strip_prefix("0x"));
+    let color_ok = match hex {
+        Some(digits) => matches!(digits.len(), 6 | 8) && digits.chars().all(|c| c.is_ascii_hexdigit()),
+        None => !color.is_empty() && color.chars().all(|c| c.is_ascii_alphabetic()),
+    };
+    let alpha_ok = alpha.is_none_or(|a| a.parse::<f64>().is_ok_and(|a| (0.0..=1.0).contains(&a)));
+    color_ok && alpha_ok
+}
+
+/// Check a custom x/y expression. Only arithmetic and function calls are allowed,
+/// so it can't close the quoted value and add filter options.
+pub fn is_valid_position_expression(value: &str) -> bool {
+    !value.trim().is_empty()
+        && value.len() <= 256
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || " _.+-*/(),".contains(c))
+}
+
+impl TextOverlay {
+    /// Check user-supplied values that are inserted into the filter graph
+    pub fn validate(&self) -> Result<(), String> {
+        for color in std::iter::once(&self.color).chain(&self.box_color) {
+            if !is_valid_color(color) {
+                return Err(format!("Invalid text color: {}", color));
+            }
+        }
+        for expression in self.x.iter().chain(&self.y) {
+            if !is_valid_position_expression(expression) {
+                return Err(format!("Invalid text position expression: {}", expression));
+            }
+        }
+        Ok(())
+    }
+
+    /// Centered text using default styling, as used by title cards
+    pub fn centered(text: &str, font_file: Option<String>, font_size: u32, color: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            font_file,
+            font_size,
+            color: color.to_string(),
+            box_color: None,
+            box_border: default_box_border(),
+            position: TextPosition::Center,
+            x: None,
+            y: None,
+            margin: default_margin(),
+            start: None,
+            end: None,
+        }
+    }
+
+    // x/y expressions for the anchor
+    fn coordinates(&self) -> (String, String) {
+        let m = self.margin;
+        match self.position {
+            TextPosition::TopLeft => (m.to_string(), m.to_string()),
+            TextPosition::TopCenter => ("(w-text_w)/2".to_string(), m.to_string()),
+            TextPosition::TopRight => (format!("w-text_w-{}", m), m.to_string()),
+            TextPosition::Center => ("(w-text_w)/2".to_string(), "(h-text_h)/2".to_string()),
+            TextPosition::BottomLeft => (m.to_string(), format!("h-text_h-{}", m)),
+            TextPosition::BottomCenter => ("(w-text_w)/2".to_string(), format!("h-text_h-{}", m)),
+            TextPosition::BottomRight => (format!("w-text_w-{}", m), format!("h-text_h-{}", m)),
+            TextPosition::LowerThird => (m.to_string(), "h*2/3".to_string()),
+            TextPosition::Custom => (
+                self.x.clone().unwrap_or_else(|| "0".to_string()),
+                self.y.clone().unwrap_or_else(|| "0".to_string()),
+            ),
+        }
+    }
+
+    /// Build the drawtext filter for this layer
+    pub fn drawtext_filter(&self) -> String {
+        let mut params = Vec::new();
+
+        if let Some(font) = &self.font_file {
+            params.push(format!("fontfile={}", escape_filter_value(font)));
+        }
+        params.push(format!("text={}", escape_drawtext_text(&self.text)));
+        params.push("expansion=none".to_string());
+        params.push(format!("fontsize={}", self.font_size));
+        params.push(format!("fontcolor={}", self.color));
+
+        if let Some(box_color) = &self.box_color {
+            params.push("box=1".to_string());
+            params.push(format!("boxcolor={}", box_color));
+            params.push(format!("boxborderw={}", self.box_border));
+        }
+
+        let (x, y) = self.coordinates();
+        params.push(format!("x='{}'", x));
+        params.push(format!("y='{}'", y));
+
+        if let Some(enable) = enable_expression(self.start, self.end) {
+            params.push(format!("enable='{}'", enable));
+        }
+
+        format!("drawtext={}", params.join(":"))
+    }
+}
+
+/// Timeline enable expression for an optional time range
+pub fn enable_expression(start: Option<f64>, end: Option<f64>) -> Option<String> {
+    match (start, end) {
+        (Some(s), Some(e)) => Some(format!("between(t,{},{})", s, e)),
+        (Some(s), None) => Some(format!("gte(t,{})", s)),
+        (None, Some(e)) => Some(format!("lte(t,{})", e)),
+        (None, None) => None,
+    }
+}
+
+/// Full-screen title clip: text over a solid color background
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TitleCard {
+    pub text: String,
+    #[serde(default = "default_background")]
+    pub background: String,
+    pub font_file: Option<String>,
+    #[serde(default = "default_title_font_size")]
+    pub font_size: u32,
+    #[serde(default = "default_color")]
+    pub color: String,
+}
+
+fn default_background() -> String {
+    "black".to_string()
+}
+
+fn default_title_font_size() -> u32 {
+    72
+}
+
+/// Silent stereo audio source paired with generated video so clips can be concatenated
+pub const SILENT_AUDIO_SOURCE: &str = "anullsrc=r=48000:cl=stereo";
+
+/// lavfi solid color video source, used for title cards and timeline gaps
+pub fn color_source(color: &str, width: u32, height: u32, duration: f64) -> String {
+    format!("color=c={}:s={}x{}:d={}:r=30", color, width, height, duration)
+}
+
+impl TitleCard {
+    /// Check the colors, which are inserted into the filter graph
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_valid_color(&self.background) {
+            return Err(format!("Invalid title card background: {}", self.background));
+        }
+        self.text_overlay().validate()
+    }
+
+    /// lavfi color source for the background
+    pub fn color_source(&self, width: u32, height: u32, duration: f64) -> String {
+        color_source(&self.background, width, height, duration)
+    }
+
+    /// Centered text layer for the card
+    pub fn text_overlay(&self) -> TextOverlay {
+        TextOverlay::centered(&self.text, self.font_file.clone(), self.font_size, &self.color)
+    }
+}
@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+
+use super::clip::Clip;
+use super::text_overlay::enable_expression;
+use super::transitions::Transition;
+
+/// Gaps shorter than this are rounding noise from the UI (about a frame at 25 fps)
+pub const GAP_TOLERANCE: f64 = 0.04;
+
+/// Empty stretch of a track, rendered as black and silence
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Gap {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl Gap {
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// A clip or gap on a track, in timeline order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TrackItem {
+    Clip(Clip),
+    Gap(Gap),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Track {
+    pub index: u32,
+    pub items: Vec<TrackItem>,
+}
+
+impl Track {
+    /// The track as a sequence from 0 to `until`, with gaps where it has no clips
+    pub fn sequence(&self, until: f64) -> Vec<SequenceItem<'_>> {
+        let mut items: Vec<SequenceItem> = self.items.iter()
+            .map(|item| match item {
+                TrackItem::Clip(clip) => SequenceItem::Clip(clip),
+                TrackItem::Gap(gap) => SequenceItem::Gap(gap.duration()),
+            })
+            .collect();
+        let end = self.spans().iter().map(|(_, end)| *end).fold(0.0, f64::max);
+        if until - end > GAP_TOLERANCE {
+            items.push(SequenceItem::Gap(until - end));
+        }
+        items
+    }
+
+    /// Timeline ranges covered by the track's clips
+    pub fn spans(&self) -> Vec<(f64, f64)> {
+        self.items.iter()
+            .filter_map(|item| match item {
+                TrackItem::Clip(clip) => Some((clip.start, clip.end)),
+                TrackItem::Gap(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// The edit: clips placed on numbered tracks, with the gaps between them made explicit.
+/// Tracks play at the same time; higher tracks are drawn over lower ones where they have clips.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timeline {
+    pub tracks: Vec<Track>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    MissingMedia,
+    TrimOutOfRange,
+    Overlap,
+    InvalidPlacement,
+}
+
+/// A problem that would make the timeline render incorrectly
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimelineIssue {
+    pub clip_id: String,
+    pub kind: IssueKind,
+    pub message: String,
+}
+
+/// What a track plays at each point, in order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SequenceItem<'a> {
+    Clip(&'a Clip),
+    Gap(f64),  // Duration of black/silence
+}
+
+// Track items with gaps filled in from 0 to the last clip
+fn track_items(mut clips: Vec<Clip>) -> Vec<TrackItem> {
+    clips.sort_by(|a, b| a.start.total_cmp(&b.start));
+    let mut items = Vec::new();
+    let mut cursor = 0.0;
+    for clip in clips {
+        if clip.start - cursor > GAP_TOLERANCE {
+            items.push(TrackItem::Gap(Gap { start: cursor, end: clip.start }));
+        }
+        cursor = cursor.max(clip.end);
+        items.push(TrackItem::Clip(clip));
+    }
+    items
+}
+
+impl Timeline {
+    /// Build a timeline from clips as the frontend stores them (a flat list with track numbers)
+    pub fn from_clips(clips: Vec<Clip>) -> Self {
+        let mut indices: Vec<u32> = clips.iter().map(|c| c.track).collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut clips = clips;
+        let tracks = indices
+            .into_iter()
+            .map(|index| {
+                let (on_track, rest): (Vec<Clip>, Vec<Clip>) = clips.drain(..).partition(|c| c.track == index);
+                clips = rest;
+                Track { index, items: track_items(on_track) }
+            })
+            .collect();
+        Self { tracks }
+    }
+
+    /// Every clip on every track
+    pub fn clips(&self) -> impl Iterator<Item = &Clip> {
+        self.tracks.iter().flat_map(|t| t.items.iter()).filter_map(|item| match item {
+            TrackItem::Clip(clip) => Some(clip),
+            TrackItem::Gap(_) => None,
+        })
+    }
+
+    /// End of the last clip
+    pub fn duration(&self) -> f64 {
+        self.clips().map(|c| c.end).fold(0.0, f64::max)
+    }
+
+    /// Check media, trims and placement. `source_duration` returns the probed
+    /// duration of a media file, or None when it is missing.
+    pub fn validate(&self, source_duration: impl Fn(&str) -> Option<f64>) -> Vec<TimelineIssue> {
+        let mut issues = Vec::new();
+        let issue = |clip: &Clip, kind: IssueKind, message: String| TimelineIssue {
+            clip_id: clip.id.clone(),
+            kind,
+            message,
+        };
+
+        for track in &self.tracks {
+            let mut previous: Option<&Clip> = None;
+            for clip in track.items.iter().filter_map(|item| match item {
+                TrackItem::Clip(clip) => Some(clip),
+                TrackItem::Gap(_) => None,
+            }) {
+                if clip.title_card.is_none() {
+                    match source_duration(&clip.path) {
+                        None => issues.push(issue(clip, IssueKind::MissingMedia, format!("Media not found: {}", clip.path))),
+                        Some(duration) => {
+                            let probed = Clip { duration, ..clip.clone() };
+                            if let Err(e) = probed.validate_trim() {
+                                issues.push(issue(clip, IssueKind::TrimOutOfRange, e));
+                            }
+                        }
+                    }
+                } else if clip.trim_end <= clip.trim_start {
+                    issues.push(issue(clip, IssueKind::TrimOutOfRange, format!("Clip {} has an empty trim range", clip.id)));
+                }
+
+                if clip.start < 0.0 || (clip.end - clip.start - clip.output_duration()).abs() > GAP_TOLERANCE {
+                    issues.push(issue(
+                        clip,
+                        IssueKind::InvalidPlacement,
+                        format!(
+                            "Clip {} spans {:.3}-{:.3}s but renders for {:.3}s",
+                            clip.id, clip.start, clip.end, clip.output_duration()
+                        ),
+                    ));
+                }
+
+                if let Some(prev) = previous.filter(|p| clip.start < p.end - GAP_TOLERANCE) {
+                    issues.push(issue(
+                        clip,
+                        IssueKind::Overlap,
+                        format!("Clip {} overlaps clip {} on track {}", clip.id, prev.id, track.index),
+                    ));
+                }
+                if previous.is_none_or(|p| clip.end > p.end) {
+                    previous = Some(clip);
+                }
+            }
+        }
+        issues
+    }
+}
+
+/// Re-point transitions (indexed by clip) at sequence positions. A transition can't
+/// cross a gap, since there is nothing to blend with.
+pub fn remap_transitions(sequence: &[SequenceItem], transitions: &[Transition]) -> Result<Vec<Transition>, String> {
+    let clip_positions: Vec<usize> = sequence
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| matches!(item, SequenceItem::Clip(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    transitions
+        .iter()
+        .map(|t| {
+            let (from, to) = match (clip_positions.get(t.after_clip), clip_positions.get(t.after_clip + 1)) {
+                (Some(from), Some(to)) => (*from, *to),
+                _ => return Err(format!("Transition after clip {} has no following clip", t.after_clip)),
+            };
+            if to != from + 1 {
+                return Err(format!("Transition after clip {} crosses a gap in the timeline", t.after_clip));
+            }
+            Ok(Transition { after_clip: from, ..t.clone() })
+        })
+        .collect()
+}
+
+/// A rendered track, as input to `build_layer_graph`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackLayer {
+    pub spans: Vec<(f64, f64)>,  // Where the track has clips (timeline time)
+    pub has_audio: bool,
+}
+
+/// Stack rendered tracks into [vout] and [aout]; input i is layers[i], lowest track first.
+/// Tracks above the base are only shown while they have clips, so their gaps reveal the
+/// tracks below. Returns the graph and whether it produces [aout].
+pub fn build_layer_graph(layers: &[TrackLayer]) -> (String, bool) {
+    let mut chains = Vec::new();
+
+    let mut video = "[0:v]".to_string();
+    for (i, layer) in layers.iter().enumerate().skip(1) {
+        let enable: Vec<String> = layer.spans.iter()
+            .filter_map(|(start, end)| enable_expression(Some(*start), Some(*end)))
+            .collect();
+        if enable.is_empty() {
+            continue;
+        }
+        let out = format!("[layer{}]", i);
+        chains.push(format!("{}[{}:v]overlay=eof_action=pass:enable='{}'{}", video, i, enable.join("+"), out));
+        video = out;
+    }
+    chains.push(format!("{}format=yuv420p[vout]", video));
+
+    let audio: Vec<String> = layers.iter()
+        .enumerate()
+        .filter(|(_, layer)| layer.has_audio)
+        .map(|(i, _)| format!("[{}:a]", i))
+        .collect();
+    match audio.len() {
+        0 => {}
+        1 => chains.push(format!("{}anull[aout]", audio[0])),
+        n => chains.push(format!("{}amix=inputs={}:duration=longest:normalize=0[aout]", audio.concat(), n)),
+    }
+
+    (chains.join(";"), !audio.is_empty())
+}
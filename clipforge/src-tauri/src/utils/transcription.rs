@@ -0,0 +1,237 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use super::subtitles::{format_timestamp, write_vtt, Cue};
+
+/// A word with its own timing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// A transcribed phrase, with word timings when the backend provides them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Full result of a transcription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub language: String,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    /// Plain text of all segments
+    pub fn text(&self) -> String {
+        self.segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    /// One caption cue per segment. With `word_timestamps`, each word after the first
+    /// is preceded by a WebVTT timestamp tag (`<00:00:01.500>word`).
+    pub fn to_cues(&self, word_timestamps: bool) -> Vec<Cue> {
+        self.segments.iter()
+            .map(|segment| {
+                let text = if word_timestamps && !segment.words.is_empty() {
+                    segment.words.iter()
+                        .enumerate()
+                        .map(|(i, word)| {
+                            if i == 0 {
+                                word.text.clone()
+                            } else {
+                                format!("<{}>{}", format_timestamp(word.start, '.'), word.text)
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                } else {
+                    segment.text.clone()
+                };
+                Cue { start: segment.start, end: segment.end, text }
+            })
+            .collect()
+    }
+
+    /// WebVTT captions for the transcript
+    pub fn to_vtt(&self, word_timestamps: bool) -> String {
+        write_vtt(&self.to_cues(word_timestamps))
+    }
+}
+
+/// A speech-to-text engine that turns an audio file into a transcript
+pub trait TranscriptionBackend {
+    /// Short name for logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Audio format the backend expects: (extension, ffmpeg codec)
+    fn audio_format(&self) -> (&'static str, &'static str);
+
+    /// Transcribe `audio_path`; `language` is an ISO 639-1 code, or None to auto-detect
+    fn transcribe(&self, audio_path: &Path, language: Option<&str>) -> Result<Transcript, String>;
+}
+
+/// Which backend to use, as chosen by the frontend. Hosted transcription is
+/// called directly from the frontend (transcription-service.ts), not from here.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum TranscriptionSettings {
+    WhisperCpp {
+        binary_path: Option<String>,  // whisper.cpp CLI (`whisper-cli` on PATH when None)
+        model_path: String,           // ggml model file, e.g. ggml-base.en.bin
+        threads: Option<u32>,
+    },
+}
+
+impl TranscriptionSettings {
+    /// Build the configured backend
+    pub fn backend(&self) -> Result<Box<dyn TranscriptionBackend + Send>, String> {
+        match self {
+            TranscriptionSettings::WhisperCpp { binary_path, model_path, threads } => {
+                if !Path::new(model_path).exists() {
+                    return Err(format!("Whisper model not found: {}", model_path));
+                }
+                Ok(Box::new(WhisperCppBackend {
+                    binary: PathBuf::from(binary_path.as_deref().unwrap_or("whisper-cli")),
+                    model: PathBuf::from(model_path),
+                    threads: *threads,
+                }))
+            }
+        }
+    }
+}
+
+/// Local, CPU-only transcription with the whisper.cpp command line tool
+pub struct WhisperCppBackend {
+    pub binary: PathBuf,
+    pub model: PathBuf,
+    pub threads: Option<u32>,
+}
+
+impl WhisperCppBackend {
+    /// Arguments for a run writing full JSON (with token timings) to `<output_prefix>.json`
+    pub fn args(&self, audio_path: &Path, output_prefix: &Path, language: Option<&str>) -> Vec<String> {
+        let mut args = vec![
+            "-m".to_string(), self.model.to_string_lossy().to_string(),
+            "-f".to_string(), audio_path.to_string_lossy().to_string(),
+            "-l".to_string(), language.unwrap_or("auto").to_string(),
+            "-ng".to_string(),   // CPU only
+            "-np".to_string(),   // No progress/info prints
+            "-ojf".to_string(),  // Full JSON output, includes token offsets
+            "-of".to_string(), output_prefix.to_string_lossy().to_string(),
+        ];
+        if let Some(threads) = self.threads {
+            args.extend(["-t".to_string(), threads.max(1).to_string()]);
+        }
+        args
+    }
+}
+
+impl TranscriptionBackend for WhisperCppBackend {
+    fn name(&self) -> &'static str {
+        "whisper.cpp"
+    }
+
+    fn audio_format(&self) -> (&'static str, &'static str) {
+        // whisper.cpp reads 16-bit PCM WAV
+        ("wav", "pcm_s16le")
+    }
+
+    fn transcribe(&self, audio_path: &Path, language: Option<&str>) -> Result<Transcript, String> {
+        let output_prefix = audio_path.with_extension("whisper");
+        let json_path = output_prefix.with_extension("json");
+
+        let output = Command::new(&self.binary)
+            .args(self.args(audio_path, &output_prefix, language))
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", self.binary.display(), e))?;
+        if !output.status.success() {
+            return Err(format!("whisper.cpp failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+
+        let json = std::fs::read_to_string(&json_path)
+            .map_err(|e| format!("Failed to read whisper.cpp output: {}", e))?;
+        let _ = std::fs::remove_file(&json_path);
+        parse_whisper_cpp_json(&json)
+    }
+}
+
+#[derive(Deserialize)]
+struct WhisperCppOutput {
+    result: Option<WhisperCppResult>,
+    transcription: Vec<WhisperCppSegment>,
+}
+
+#[derive(Deserialize)]
+struct WhisperCppResult {
+    language: String,
+}
+
+#[derive(Deserialize)]
+struct WhisperCppSegment {
+    offsets: WhisperCppOffsets,
+    text: String,
+    #[serde(default)]
+    tokens: Vec<WhisperCppToken>,
+}
+
+#[derive(Deserialize)]
+struct WhisperCppToken {
+    text: String,
+    offsets: WhisperCppOffsets,
+}
+
+#[derive(Deserialize)]
+struct WhisperCppOffsets {
+    from: u64,  // Milliseconds
+    to: u64,
+}
+
+/// Parse whisper.cpp `-ojf` output. Tokens are joined into words: a token starting
+/// with whitespace begins a new word, others (sub-words, punctuation) extend the current one.
+pub fn parse_whisper_cpp_json(json: &str) -> Result<Transcript, String> {
+    let output: WhisperCppOutput = serde_json::from_str(json)
+        .map_err(|e| format!("Invalid whisper.cpp output: {}", e))?;
+
+    let segments = output.transcription.into_iter()
+        .filter(|segment| !segment.text.trim().is_empty())
+        .map(|segment| {
+            let mut words: Vec<TranscriptWord> = Vec::new();
+            for token in &segment.tokens {
+                // Special tokens look like [_BEG_] or [_TT_150]
+                if token.text.starts_with("[_") || token.text.trim().is_empty() {
+                    continue;
+                }
+                let start = token.offsets.from as f64 / 1000.0;
+                let end = token.offsets.to as f64 / 1000.0;
+                match words.last_mut() {
+                    Some(word) if !token.text.starts_with(char::is_whitespace) => {
+                        word.text.push_str(&token.text);
+                        word.end = end;
+                    }
+                    _ => words.push(TranscriptWord { start, end, text: token.text.trim().to_string() }),
+                }
+            }
+
+            TranscriptSegment {
+                start: segment.offsets.from as f64 / 1000.0,
+                end: segment.offsets.to as f64 / 1000.0,
+                text: segment.text.trim().to_string(),
+                words,
+            }
+        })
+        .collect();
+
+    Ok(Transcript {
+        language: output.result.map_or_else(|| "auto".to_string(), |r| r.language),
+        segments,
+    })
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+/// Transition styles between adjacent clips
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionKind {
+    Crossfade,
+    DipToBlack,
+    WipeLeft,
+    WipeRight,
+    WipeUp,
+    WipeDown,
+    SlideLeft,
+    SlideRight,
+    SlideUp,
+    SlideDown,
+}
+
+impl TransitionKind {
+    /// Name of the matching xfade transition
+    pub fn xfade_name(&self) -> &'static str {
+        match self {
+            TransitionKind::Crossfade => "fade",
+            TransitionKind::DipToBlack => "fadeblack",
+            TransitionKind::WipeLeft => "wipeleft",
+            TransitionKind::WipeRight => "wiperight",
+            TransitionKind::WipeUp => "wipeup",
+            TransitionKind::WipeDown => "wipedown",
+            TransitionKind::SlideLeft => "slideleft",
+            TransitionKind::SlideRight => "slideright",
+            TransitionKind::SlideUp => "slideup",
+            TransitionKind::SlideDown => "slidedown",
+        }
+    }
+}
+
+/// Transition at the boundary between clip `after_clip` and the clip that follows it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    pub after_clip: usize,
+    pub kind: TransitionKind,
+    pub duration: f64,  // Overlap in seconds
+}
+
+/// Frame rate all clips are conformed to before xfade (it needs matching timebases)
+pub const TRANSITION_FPS: u32 = 30;
+
+// Transition at a given boundary, if any
+fn transition_at(transitions: &[Transition], boundary: usize) -> Option<&Transition> {
+    transitions.iter().find(|t| t.after_clip == boundary)
+}
+
+/// Check transitions against the clip durations they overlap
+pub fn validate_transitions(durations: &[f64], transitions: &[Transition]) -> Result<(), String> {
+    for (i, t) in transitions.iter().enumerate() {
+        if t.after_clip + 1 >= durations.len() {
+            return Err(format!("Transition after clip {} has no following clip", t.after_clip));
+        }
+        if transitions[..i].iter().any(|other| other.after_clip == t.after_clip) {
+            return Err(format!("Multiple transitions after clip {}", t.after_clip));
+        }
+        if t.duration.is_nan() || t.duration <= 0.0 {
+            return Err(format!("Transition after clip {} must have a positive duration", t.after_clip));
+        }
+    }
+
+    // Each clip must be long enough for the transitions on both of its edges
+    for (i, duration) in durations.iter().enumerate() {
+        let incoming = if i > 0 { transition_at(transitions, i - 1).map_or(0.0, |t| t.duration) } else { 0.0 };
+        let outgoing = transition_at(transitions, i).map_or(0.0, |t| t.duration);
+        if incoming + outgoing >= *duration {
+            return Err(format!(
+                "Clip {} ({:.2}s) is too short for its transitions ({:.2}s)",
+                i, duration, incoming + outgoing
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Total program length once transition overlaps are removed
+pub fn timeline_duration(durations: &[f64], transitions: &[Transition]) -> f64 {
+    let overlap: f64 = (0..durations.len().saturating_sub(1))
+        .filter_map(|boundary| transition_at(transitions, boundary))
+        .map(|t| t.duration)
+        .sum();
+    durations.iter().sum::<f64>() - overlap
+}
+
+/// Start of each clip on the program timeline, accounting for transition overlaps
+pub fn clip_offsets(durations: &[f64], transitions: &[Transition]) -> Vec<f64> {
+    let mut offsets = Vec::with_capacity(durations.len());
+    let mut position = 0.0;
+    for (i, duration) in durations.iter().enumerate() {
+        if i > 0 {
+            position -= transition_at(transitions, i - 1).map_or(0.0, |t| t.duration);
+        }
+        offsets.push(position);
+        position += duration;
+    }
+    offsets
+}
+
+/// Build a filter_complex graph joining inputs 0..N with xfade/acrossfade at
+/// transition boundaries and hard cuts elsewhere. Audio is padded or cut to each
+/// input's video duration so crossfades line up with the xfades; inputs without
+/// audio (`has_audio[i] == false`) get silence of that duration instead.
+/// Outputs are labelled `[vout]` and `[aout]`.
+pub fn build_transition_graph(durations: &[f64], has_audio: &[bool], transitions: &[Transition]) -> String {
+    let mut chains = Vec::new();
+
+    // Conform every input so xfade/concat see identical timebases and formats
+    for (i, duration) in durations.iter().enumerate() {
+        chains.push(format!("[{}:v]fps={},settb=AVTB,format=yuv420p[v{}]", i, TRANSITION_FPS, i));
+        if has_audio.get(i).copied().unwrap_or(true) {
+            chains.push(format!(
+                "[{}:a]aformat=sample_rates=48000:channel_layouts=stereo,asetpts=PTS-STARTPTS,apad,atrim=duration={:.3}[a{}]",
+                i, duration, i
+            ));
+        } else {
+            chains.push(format!("anullsrc=channel_layout=stereo:sample_rate=48000,atrim=duration={:.3}[a{}]", duration, i));
+        }
+    }
+
+    if durations.len() == 1 {
+        chains.push("[v0]null[vout]".to_string());
+        chains.push("[a0]anull[aout]".to_string());
+        return chains.join(";");
+    }
+
+    let mut video = "[v0]".to_string();
+    let mut audio = "[a0]".to_string();
+    let mut length = durations[0];
+
+    for boundary in 0..durations.len() - 1 {
+        let next = boundary + 1;
+        let last = next == durations.len() - 1;
+        let out_video = if last { "[vout]".to_string() } else { format!("[vx{}]", boundary) };
+        let out_audio = if last { "[aout]".to_string() } else { format!("[ax{}]", boundary) };
+
+        match transition_at(transitions, boundary) {
+            Some(t) => {
+                chains.push(format!(
+                    "{}[v{}]xfade=transition={}:duration={:.3}:offset={:.3}{}",
+                    video, next, t.kind.xfade_name(), t.duration, length - t.duration, out_video
+                ));
+                chains.push(format!("{}[a{}]acrossfade=d={:.3}{}", audio, next, t.duration, out_audio));
+                length += durations[next] - t.duration;
+            }
+            None => {
+                chains.push(format!(
+                    "{}{}[v{}][a{}]concat=n=2:v=1:a=1{}{}",
+                    video, audio, next, next, out_video, out_audio
+                ));
+                length += durations[next];
+            }
+        }
+
+        video = out_video;
+        audio = out_audio;
+    }
+
+    chains.join(";")
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+use super::ffmpeg::FfmpegBuilder;
+
+/// How `trim_clip` cuts the source
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrimMode {
+    /// Stream copy from the keyframe at or before the in point (fast, may be off by a GOP)
+    #[default]
+    Fast,
+    /// Re-encode only the partial GOPs at the in/out points and stream copy the rest
+    Accurate,
+    /// Re-encode the whole range (slowest, always frame accurate)
+    Reencode,
+}
+
+/// Cut points closer than this to a keyframe are treated as keyframe aligned
+pub const KEYFRAME_TOLERANCE: f64 = 0.001;
+
+/// One piece of a smart-rendered trim, in source time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CutSegment {
+    pub start: f64,
+    pub end: f64,
+    pub copy: bool,  // Stream copy (starts on a keyframe) or re-encode
+}
+
+impl CutSegment {
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Split `start..end` into segments for smart rendering: the head up to the first
+/// keyframe and the tail after the last keyframe are re-encoded, the keyframe-aligned
+/// middle is stream copied. Falls back to a single re-encoded segment when the range
+/// doesn't contain a full GOP.
+pub fn plan_smart_cut(keyframes: &[f64], start: f64, end: f64) -> Vec<CutSegment> {
+    let first = keyframes.iter().copied().find(|k| *k >= start - KEYFRAME_TOLERANCE);
+    let last = keyframes.iter().copied().rev().find(|k| *k <= end + KEYFRAME_TOLERANCE);
+
+    let (first, last) = match (first, last) {
+        (Some(first), Some(last)) if last - first > KEYFRAME_TOLERANCE => (first, last),
+        _ => return vec![CutSegment { start, end, copy: false }],
+    };
+
+    let mut segments = Vec::new();
+    if first - start > KEYFRAME_TOLERANCE {
+        segments.push(CutSegment { start, end: first, copy: false });
+    }
+    segments.push(CutSegment { start: first.max(start), end: last.min(end), copy: true });
+    if end - last > KEYFRAME_TOLERANCE {
+        segments.push(CutSegment { start: last, end, copy: false });
+    }
+    segments
+}
+
+/// ffprobe `-show_entries` for `SourceVideo::parse` (use with `-of default=noprint_wrappers=1`)
+pub const SOURCE_VIDEO_ENTRIES: &str = "stream=codec_name,profile,level,pix_fmt,r_frame_rate,time_base";
+
+/// Smart-rendered pieces are MPEG-TS so every piece carries its own SPS/PPS in-band.
+/// The concat demuxer only keeps the first piece's MP4 extradata, which doesn't
+/// describe the stream-copied source frames.
+pub const SMART_PART_EXTENSION: &str = "ts";
+
+/// Encoding properties of the source video stream that re-encoded pieces must match
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceVideo {
+    pub codec: String,
+    pub profile: String,
+    pub level: Option<u32>,  // level_idc, e.g. 31 for 3.1
+    pub pix_fmt: String,
+    pub frame_rate: Option<String>,  // e.g. "30000/1001"
+    pub timescale: Option<String>,  // Denominator of the stream time base
+}
+
+impl SourceVideo {
+    /// Parse ffprobe key=value output for the first video stream
+    pub fn parse(stdout: &str) -> Option<Self> {
+        let field = |name: &str| {
+            stdout.lines()
+                .filter_map(|line| line.trim().split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+                .filter(|value| !value.is_empty() && value != "N/A" && value != "unknown")
+        };
+        Some(Self {
+            codec: field("codec_name")?,
+            profile: field("profile").unwrap_or_default(),
+            level: field("level").and_then(|l| l.parse().ok()).filter(|l| *l > 0),
+            pix_fmt: field("pix_fmt")?,
+            frame_rate: field("r_frame_rate").filter(|r| r != "0/0"),
+            timescale: field("time_base").and_then(|tb| tb.split_once('/').map(|(_, den)| den.to_string())),
+        })
+    }
+
+    /// libx264 options producing a stream that can be joined with the source's
+    /// frames, or None when libx264 can't match it (not 8-bit 4:2:0 H.264)
+    pub fn matching_encoder_args(&self) -> Option<Vec<String>> {
+        if self.codec != "h264" || !matches!(self.pix_fmt.as_str(), "yuv420p" | "yuvj420p") {
+            return None;
+        }
+        let profile = match self.profile.as_str() {
+            "Constrained Baseline" | "Baseline" => "baseline",
+            "Main" => "main",
+            "High" => "high",
+            _ => return None,
+        };
+
+        let mut args: Vec<String> = ["-c:v", "libx264", "-preset", "medium", "-crf", "18", "-profile:v", profile]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        if let Some(level) = self.level {
+            args.extend(["-level:v".to_string(), format!("{}.{}", level / 10, level % 10)]);
+        }
+        args.extend(["-pix_fmt".to_string(), self.pix_fmt.clone()]);
+        if let Some(rate) = &self.frame_rate {
+            args.extend(["-r".to_string(), rate.clone()]);
+        }
+        Some(args)
+    }
+}
+
+/// Command rendering one smart-cut segment to an MPEG-TS piece: copied segments are
+/// converted to Annex B, the rest re-encoded with `encoder_args`. Audio is
+/// re-encoded in every piece so the joined stream is continuous.
+pub fn segment_builder(input: &str, segment: &CutSegment, encoder_args: &[String], output: &str) -> FfmpegBuilder {
+    let builder = FfmpegBuilder::new()
+        .input(input)
+        .trim(segment.start, segment.duration());
+    let builder = if segment.copy {
+        builder.copy_video().output_options(&["-bsf:v", "h264_mp4toannexb"])
+    } else {
+        let args: Vec<&str> = encoder_args.iter().map(String::as_str).collect();
+        builder.audio_codec("aac").output_options(&args)
+    };
+    builder.output_options(&["-f", "mpegts"]).output(output)
+}
+
+/// Command joining the pieces listed in `concat_list` into an MP4 with the source's timescale
+pub fn join_builder(concat_list: &str, source: &SourceVideo, output: &str) -> FfmpegBuilder {
+    let builder = FfmpegBuilder::new().concat(concat_list).stream_copy();
+    let builder = match &source.timescale {
+        Some(timescale) => builder.output_options(&["-video_track_timescale", timescale]),
+        None => builder,
+    };
+    builder.output(output)
+}
+
+/// Shortest half `split_ranges` will produce, in seconds
+pub const MIN_SPLIT_LENGTH: f64 = 0.1;
+
+/// Source ranges of the two halves when splitting the source range `start..end` at `at`
+pub fn split_ranges((start, end): (f64, f64), at: f64) -> Result<[(f64, f64); 2], String> {
+    if !at.is_finite() || at < start + MIN_SPLIT_LENGTH || at > end - MIN_SPLIT_LENGTH {
+        return Err(format!(
+            "Split point {:.3}s must be at least {}s inside the clip's {:.3}-{:.3}s range",
+            at, MIN_SPLIT_LENGTH, start, end
+        ));
+    }
+    Ok([(start, at), (at, end)])
+}
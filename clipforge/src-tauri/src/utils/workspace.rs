@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::clip::Clip;
+
+/// Schema version written by this build
+pub const WORKSPACE_VERSION: u32 = 2;
+
+/// Saved editor state (workspace.json)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub version: u32,
+    #[serde(default)]
+    pub clips: Vec<Clip>,
+    #[serde(default)]
+    pub playhead: f64,
+    #[serde(default)]
+    pub is_playing: bool,
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
+    #[serde(default)]
+    pub selected_clip_id: Option<String>,
+    #[serde(default)]
+    pub export_progress: f64,
+}
+
+fn default_zoom() -> f64 {
+    10.0
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self {
+            version: WORKSPACE_VERSION,
+            clips: Vec::new(),
+            playhead: 0.0,
+            is_playing: false,
+            zoom: default_zoom(),
+            selected_clip_id: None,
+            export_progress: 0.0,
+        }
+    }
+}
+
+/// A loaded workspace, the version it was stored as and what had to be dropped to load it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoadedWorkspace {
+    pub workspace: Workspace,
+    pub stored_version: u32,
+    pub warnings: Vec<String>,
+}
+
+impl LoadedWorkspace {
+    /// Whether the file on disk is older than this build and should be rewritten
+    pub fn was_migrated(&self) -> bool {
+        self.stored_version < WORKSPACE_VERSION
+    }
+
+    /// A fresh workspace in place of one that couldn't be read, with the reason as a warning
+    pub fn replacing_unreadable(warning: String) -> Self {
+        Self { workspace: Workspace::default(), stored_version: WORKSPACE_VERSION, warnings: vec![warning] }
+    }
+}
+
+// Upgrades one version to the next; MIGRATIONS[i] takes version i + 1 to i + 2
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+const MIGRATIONS: [Migration; 1] = [migrate_v1_to_v2];
+
+// v1 files have no version field. The oldest only stored id/name/path/start/end/duration
+// per clip; trims and tracks were added later without a schema bump, so fill them in.
+fn migrate_v1_to_v2(state: &mut Value) -> Result<(), String> {
+    let clips = match state.get_mut("clips") {
+        Some(Value::Array(clips)) => clips,
+        // Anything else is reported by `load`
+        _ => return Ok(()),
+    };
+
+    for clip in clips.iter_mut() {
+        let Some(clip) = clip.as_object_mut() else { continue };
+        let start = clip.get("start").and_then(Value::as_f64).unwrap_or(0.0);
+        let end = clip.get("end").and_then(Value::as_f64).unwrap_or(start);
+        let duration = clip.get("duration").and_then(Value::as_f64).unwrap_or(end - start);
+
+        clip.entry("track").or_insert(Value::from(0));
+        clip.entry("trimStart").or_insert(Value::from(0.0));
+        clip.entry("trimEnd").or_insert(Value::from((end - start).min(duration)));
+    }
+    Ok(())
+}
+
+/// Version stored in a workspace file (files without one are version 1)
+pub fn stored_version(state: &Value) -> Result<u32, String> {
+    match state.get("version") {
+        None | Some(Value::Null) => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| format!("Invalid workspace version: {}", version)),
+    }
+}
+
+/// Run the migration chain on raw workspace JSON, upgrading it to WORKSPACE_VERSION
+pub fn migrate(mut state: Value) -> Result<(Value, u32), String> {
+    if !state.is_object() {
+        return Err("Workspace must be a JSON object".to_string());
+    }
+    let stored = stored_version(&state)?;
+    if stored > WORKSPACE_VERSION {
+        return Err(format!(
+            "Workspace was saved by a newer version of ClipForge (schema {}, this build reads up to {})",
+            stored, WORKSPACE_VERSION
+        ));
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(stored as usize - 1) {
+        migration(&mut state).map_err(|e| format!("Failed to upgrade workspace from version {}: {}", i + 1, e))?;
+        state["version"] = Value::from(i as u32 + 2);
+    }
+    Ok((state, stored))
+}
+
+/// Parse a workspace file of any supported version. Clips that fail to parse are
+/// skipped and invalid editor settings reset, with a warning for each; only files
+/// that can't be read at all (bad JSON, newer schema) are an error.
+pub fn load(content: &str) -> Result<LoadedWorkspace, String> {
+    let state: Value = serde_json::from_str(content)
+        .map_err(|e| format!("Workspace is not valid JSON: {}", e))?;
+    let (mut state, stored_version) = migrate(state)?;
+    let mut warnings = Vec::new();
+
+    // Parse clips one at a time so a single bad clip doesn't lose the rest
+    let raw_clips = match state.as_object_mut().and_then(|o| o.remove("clips")) {
+        Some(Value::Array(clips)) => clips,
+        Some(Value::Null) | None => Vec::new(),
+        Some(other) => {
+            warnings.push(format!("Ignored workspace clips: expected a list, got {}", other));
+            Vec::new()
+        }
+    };
+    let mut clips = Vec::new();
+    for (i, raw) in raw_clips.into_iter().enumerate() {
+        let id = raw.get("id").and_then(Value::as_str).map_or_else(|| format!("#{}", i + 1), str::to_string);
+        match serde_json::from_value::<Clip>(raw) {
+            Ok(clip) => clips.push(clip),
+            Err(e) => warnings.push(format!("Skipped clip {}: {}", id, e)),
+        }
+    }
+
+    let mut workspace = serde_json::from_value::<Workspace>(state).unwrap_or_else(|e| {
+        warnings.push(format!("Reset editor settings: {}", e));
+        Workspace::default()
+    });
+    if workspace.selected_clip_id.as_ref().is_some_and(|id| !clips.iter().any(|c| &c.id == id)) {
+        workspace.selected_clip_id = None;
+    }
+    workspace.clips = clips;
+    Ok(LoadedWorkspace { workspace, stored_version, warnings })
+}
+
+/// Parse state sent by the running frontend, which is always the current schema
+pub fn parse_current(content: &str) -> Result<Workspace, String> {
+    let mut state: Value = serde_json::from_str(content)
+        .map_err(|e| format!("Workspace is not valid JSON: {}", e))?;
+    let object = state.as_object_mut().ok_or("Workspace must be a JSON object")?;
+    object.entry("version").or_insert(Value::from(WORKSPACE_VERSION));
+
+    let workspace: Workspace = serde_json::from_value(state)
+        .map_err(|e| format!("Invalid workspace state: {}", e))?;
+    if workspace.version != WORKSPACE_VERSION {
+        return Err(format!("Expected workspace version {}, got {}", WORKSPACE_VERSION, workspace.version));
+    }
+    Ok(workspace)
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::audio_export::{
+        build_audio_concat_graph, build_audio_tracks_graph, reserve_output_path, unique_output_path, AudioExtractOptions, AudioFormat, AudioSegment,
+    };
+    use clipforge_lib::utils::ffmpeg::FfmpegBuilder;
+
+    fn segment(duration: f64) -> AudioSegment {
+        AudioSegment { duration, gain: 1.0, speed: None, reverse: false, fade_in: None, fade_out: None }
+    }
+
+    #[test]
+    fn test_format_codecs() {
+        assert_eq!(AudioFormat::Wav.codec(), "pcm_s16le");
+        assert_eq!(AudioFormat::Aac.extension(), "m4a");
+        assert_eq!(AudioFormat::Opus.codec(), "libopus");
+        assert!(AudioFormat::Flac.is_lossless());
+        assert!(!AudioFormat::Mp3.is_lossless());
+
+        assert!(AudioFormat::Mp3.check_output_path("/out/mix.MP3").is_ok());
+        assert!(AudioFormat::Aac.check_output_path("/out/mix.aac").is_ok());
+        assert!(AudioFormat::Wav.check_output_path("/out/mix.mp3").is_err());
+        assert!(AudioFormat::Flac.check_output_path("/out/mix").is_err());
+    }
+
+    #[test]
+    fn test_options_validation_and_range() {
+        assert!(AudioExtractOptions::transcription().validate().is_ok());
+
+        let opus = AudioExtractOptions { format: AudioFormat::Opus, sample_rate: Some(44100), ..Default::default() };
+        assert!(opus.validate().is_err());
+
+        let surround = AudioExtractOptions { channels: Some(6), ..Default::default() };
+        assert!(surround.validate().is_err());
+
+        let backwards = AudioExtractOptions { start: Some(5.0), end: Some(2.0), ..Default::default() };
+        assert!(backwards.validate().is_err());
+
+        let ranged = AudioExtractOptions { start: Some(2.0), end: Some(5.0), ..Default::default() };
+        assert_eq!(ranged.range(), Some((2.0, Some(3.0))));
+        let open_ended = AudioExtractOptions { start: Some(2.0), ..Default::default() };
+        assert_eq!(open_ended.range(), Some((2.0, None)));
+        assert_eq!(AudioExtractOptions::default().range(), None);
+    }
+
+    #[test]
+    fn test_unique_output_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = unique_output_path(dir.path(), "clip", "wav");
+        assert_eq!(first, dir.path().join("clip.wav"));
+
+        std::fs::write(&first, b"").unwrap();
+        std::fs::write(dir.path().join("clip-1.wav"), b"").unwrap();
+        assert_eq!(unique_output_path(dir.path(), "clip", "wav"), dir.path().join("clip-2.wav"));
+    }
+
+    #[test]
+    fn test_reserve_output_path_creates_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = reserve_output_path(dir.path(), "clip.transcribe", "wav").unwrap();
+        let second = reserve_output_path(dir.path(), "clip.transcribe", "wav").unwrap();
+
+        assert_eq!(first, dir.path().join("clip.transcribe.wav"));
+        assert_eq!(second, dir.path().join("clip.transcribe-1.wav"));
+        assert!(first.exists() && second.exists());
+    }
+
+    #[test]
+    fn test_audio_concat_graph() {
+        let mut second = segment(4.0);
+        second.gain = 0.5;
+        second.speed = Some(2.0);
+        second.fade_out = Some(5.0);
+
+        assert_eq!(
+            build_audio_concat_graph(&[segment(3.0), second]),
+            "[0:a]aformat=sample_rates=48000:channel_layouts=stereo,volume=1[a0];\
+             [1:a]aformat=sample_rates=48000:channel_layouts=stereo,atempo=2,volume=0.5,afade=t=out:st=0:d=2[a1];\
+             [a0][a1]concat=n=2:v=0:a=1[aout]"
+        );
+    }
+
+    #[test]
+    fn test_audio_tracks_graph_mixes_tracks() {
+        let graph = build_audio_tracks_graph(&[vec![segment(3.0), segment(2.0)], vec![segment(5.0)]]);
+        assert!(graph.contains("[a0][a1]concat=n=2:v=0:a=1[track0]"));
+        assert!(graph.contains("[2:a]aformat=sample_rates=48000:channel_layouts=stereo,volume=1[a2]"));
+        assert!(graph.contains("[a2]concat=n=1:v=0:a=1[track1]"));
+        assert!(graph.ends_with("[track0][track1]amix=inputs=2:duration=longest:normalize=0[aout]"));
+
+        assert_eq!(build_audio_tracks_graph(&[vec![segment(3.0)]]), build_audio_concat_graph(&[segment(3.0)]));
+    }
+
+    #[test]
+    fn test_audio_extraction_args() {
+        let args = FfmpegBuilder::new()
+            .input("/test/input.mp4")
+            .seek(12.0)
+            .audio_only()
+            .audio_codec("flac")
+            .audio_sample_rate(48000)
+            .audio_channels(2)
+            .output("/test/output.flac")
+            .build_args().unwrap();
+
+        assert_eq!(
+            args.join(" "),
+            "-ss 12 -i /test/input.mp4 -vn -c:a flac -ar 48000 -ac 2 -y /test/output.flac"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::audio_mix::{build_mix_graph, AudioTrackClip, AudioTrackRole, DuckingSettings};
+
+    fn track(role: AudioTrackRole, timeline_start: f64) -> AudioTrackClip {
+        AudioTrackClip {
+            path: "track.mp3".to_string(),
+            timeline_start,
+            trim_start: 1.0,
+            trim_end: 11.0,
+            role,
+            gain_db: None,
+            fade_in: None,
+            fade_out: None,
+        }
+    }
+
+    #[test]
+    fn test_mix_without_ducking() {
+        let mut music = track(AudioTrackRole::Music, 2.5);
+        music.gain_db = Some(-12.0);
+        music.fade_out = Some(2.0);
+
+        let graph = build_mix_graph(&[music], None, 30.0, true, None);
+
+        assert_eq!(
+            graph,
+            "[1:a]atrim=start=1:end=11,asetpts=PTS-STARTPTS,volume=-12dB,afade=t=out:st=8:d=2,adelay=2500:all=1[t0];\
+             [0:a][t0]amix=inputs=2:duration=first:normalize=0[aout]"
+        );
+    }
+
+    #[test]
+    fn test_mix_with_ducking() {
+        let tracks = [track(AudioTrackRole::Music, 0.0), track(AudioTrackRole::Dialog, 4.0)];
+        let graph = build_mix_graph(&tracks, Some(&DuckingSettings::default()), 30.0, true, None);
+
+        assert!(graph.contains("[0:a][t1]amix=inputs=2:duration=first:normalize=0[fg]"));
+        assert!(graph.contains("[fg]asplit=2[fgmix][sc]"));
+        assert!(graph.contains("[t0][sc]sidechaincompress=threshold=0.05:ratio=8:attack=20:release=400[ducked]"));
+        assert!(graph.ends_with("[fgmix][ducked]amix=inputs=2:duration=first:normalize=0[aout]"));
+    }
+
+    #[test]
+    fn test_effects_do_not_drive_ducking() {
+        let tracks = [
+            track(AudioTrackRole::Music, 0.0),
+            track(AudioTrackRole::Dialog, 4.0),
+            track(AudioTrackRole::Effects, 6.0),
+        ];
+        let graph = build_mix_graph(&tracks, Some(&DuckingSettings::default()), 30.0, true, None);
+
+        assert!(graph.contains("[0:a][t1]amix=inputs=2:duration=first:normalize=0[fg]"));
+        assert!(graph.ends_with("[fgmix][ducked][t2]amix=inputs=3:duration=first:normalize=0[aout]"));
+    }
+
+    #[test]
+    fn test_ducking_ignored_without_music() {
+        let tracks = [track(AudioTrackRole::Effects, 0.0)];
+        let graph = build_mix_graph(&tracks, Some(&DuckingSettings::default()), 30.0, true, None);
+
+        assert!(!graph.contains("sidechaincompress"));
+        assert!(graph.ends_with("[0:a][t0]amix=inputs=2:duration=first:normalize=0[aout]"));
+    }
+
+    #[test]
+    fn test_mix_silent_program_with_tail() {
+        let tracks = [track(AudioTrackRole::Music, 0.0)];
+        let graph = build_mix_graph(&tracks, None, 12.5, false, Some("loudnorm=I=-14"));
+
+        assert!(graph.starts_with("aevalsrc=0:c=stereo:s=48000:d=12.5[prog];"));
+        assert!(graph.contains("[prog][t0]amix=inputs=2:duration=first:normalize=0[mix]"));
+        assert!(graph.ends_with("[mix]loudnorm=I=-14[aout]"));
+    }
+}
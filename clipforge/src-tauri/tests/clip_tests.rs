@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::clip::{Clip, ClipEffects};
+    use clipforge_lib::utils::detection::{split_at, Interval};
+
+    fn clip() -> Clip {
+        serde_json::from_str(
+            r#"{
+                "id": "a",
+                "name": "intro.mp4",
+                "path": "/clips/intro.mp4",
+                "start": 5.0,
+                "end": 13.0,
+                "duration": 20.0,
+                "track": 0,
+                "trimStart": 2.0,
+                "trimEnd": 10.0,
+                "volume": 0.5,
+                "video_fade_in": 1.0,
+                "video_fade_out": 1.0,
+                "thumbnail_path": "/clips/thumbnails/intro.mp4_thumb.jpg"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_clip_from_workspace_json() {
+        let clip = clip();
+        assert_eq!(clip.trim_start, 2.0);
+        assert_eq!(clip.trim_end, 10.0);
+        assert_eq!(clip.effects.volume, Some(0.5));
+        assert_eq!(clip.output_duration(), 8.0);
+
+        let json = serde_json::to_value(&clip).unwrap();
+        assert_eq!(json["trimStart"], 2.0);
+        assert_eq!(json["volume"], 0.5);
+    }
+
+    #[test]
+    fn test_effects_gain_and_speed() {
+        let effects = ClipEffects { volume: Some(0.5), gain_db: Some(20.0), ..Default::default() };
+        assert!((effects.linear_gain() - 5.0).abs() < 1e-9);
+        assert_eq!(ClipEffects { muted: Some(true), ..effects }.linear_gain(), 0.0);
+
+        assert_eq!(ClipEffects::default().speed_factor(), 1.0);
+        assert_eq!(ClipEffects { speed: Some(10.0), ..Default::default() }.speed_factor(), 4.0);
+    }
+
+    #[test]
+    fn test_filter_effects() {
+        let effects = ClipEffects { volume: Some(0.5), muted: Some(true), gain_db: Some(3.0), ..Default::default() };
+        assert!(effects.filter_effects().is_empty());
+        assert!(ClipEffects { speed: Some(1.0), ..Default::default() }.filter_effects().is_empty());
+
+        let graded = ClipEffects { speed: Some(2.0), video_fade_in: Some(1.0), lut_path: Some("a.cube".to_string()), ..effects };
+        assert_eq!(graded.filter_effects(), vec!["speed", "video fade in", "LUT"]);
+    }
+
+    #[test]
+    fn test_reverse_duration_limit() {
+        let reversed = ClipEffects { reverse: Some(true), ..Default::default() };
+        assert!(reversed.check_reverse(60.0).is_ok());
+        assert!(reversed.check_reverse(600.0).unwrap_err().contains("limited to 120s"));
+        assert!(ClipEffects::default().check_reverse(600.0).is_ok());
+    }
+
+    #[test]
+    fn test_virtual_trim() {
+        let trimmed = clip().with_trim(4.0, 7.0).unwrap();
+        assert_eq!(trimmed.path, "/clips/intro.mp4");
+        assert_eq!((trimmed.start, trimmed.end), (5.0, 8.0));
+
+        assert!(clip().with_trim(4.0, 25.0).is_err());
+        assert!(clip().with_trim(7.0, 4.0).is_err());
+        assert!(clip().with_trim(-1.0, 4.0).is_err());
+    }
+
+    #[test]
+    fn test_split_at() {
+        let (first, second) = clip().split_at(8.0, "b").unwrap();
+        assert_eq!((first.start, first.end, first.trim_start, first.trim_end), (5.0, 8.0, 2.0, 5.0));
+        assert_eq!((second.start, second.end, second.trim_start, second.trim_end), (8.0, 13.0, 5.0, 10.0));
+        assert_eq!(second.id, "b");
+        assert_eq!(first.effects.video_fade_out, None);
+        assert_eq!(second.effects.video_fade_in, None);
+
+        assert!(clip().split_at(5.0, "b").is_err());
+        assert!(clip().split_at(13.0, "b").is_err());
+    }
+
+    #[test]
+    fn test_split_retimed_clip() {
+        let mut fast = clip();
+        fast.effects.speed = Some(2.0);
+        fast.end = fast.start + fast.output_duration();
+        let (first, second) = fast.split_at(6.0, "b").unwrap();
+        assert_eq!(first.trim_end, 4.0);
+        assert_eq!(second.trim_start, 4.0);
+
+        let mut reversed = clip();
+        reversed.effects.reverse = Some(true);
+        let (first, second) = reversed.split_at(7.0, "b").unwrap();
+        assert_eq!((first.trim_start, first.trim_end), (8.0, 10.0));
+        assert_eq!((second.trim_start, second.trim_end), (2.0, 8.0));
+    }
+
+    #[test]
+    fn test_split_into_scenes() {
+        // Scene cuts at 4s and 7s of source split the 2-10s trim into three sub-clips
+        let scenes = split_at(&[4.0, 7.0], 2.0, 10.0);
+        let parts = clip().split_into(&scenes, "a-scene").unwrap();
+        let bounds: Vec<_> = parts.iter().map(|c| (c.start, c.end, c.trim_start, c.trim_end)).collect();
+        assert_eq!(bounds, vec![(5.0, 7.0, 2.0, 4.0), (7.0, 10.0, 4.0, 7.0), (10.0, 13.0, 7.0, 10.0)]);
+
+        let ids: Vec<&str> = parts.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "a-scene-1", "a-scene-2"]);
+        assert!(parts.iter().all(|c| c.path == "/clips/intro.mp4"));
+        assert_eq!(parts[0].effects.video_fade_in, Some(1.0));
+        assert_eq!(parts[0].effects.video_fade_out, None);
+        assert_eq!(parts[2].effects.video_fade_in, None);
+        assert_eq!(parts[2].effects.video_fade_out, Some(1.0));
+
+        assert!(clip().split_into(&[Interval { start: 1.0, end: 4.0 }], "a-scene").is_err());
+        assert!(clip().split_into(&[], "a-scene").is_err());
+    }
+
+    #[test]
+    fn test_split_reversed_clip_into_scenes() {
+        let mut reversed = clip();
+        reversed.effects.reverse = Some(true);
+        let parts = reversed.split_into(&split_at(&[4.0], 2.0, 10.0), "a-scene").unwrap();
+        let bounds: Vec<_> = parts.iter().map(|c| (c.start, c.end, c.trim_start, c.trim_end)).collect();
+        assert_eq!(bounds, vec![(5.0, 11.0, 4.0, 10.0), (11.0, 13.0, 2.0, 4.0)]);
+    }
+}
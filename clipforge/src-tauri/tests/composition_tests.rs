@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::composition::{
+        build_pip_graph, build_stack_graph, default_grid_columns, DurationSync, ExportLayout, GridSettings,
+        LayoutInput, PipAudio, PipSettings, StackDirection, StackSettings,
+    };
+    use clipforge_lib::utils::overlay::Anchor;
+
+    fn settings() -> PipSettings {
+        PipSettings {
+            anchor: Anchor::BottomRight,
+            x: None,
+            y: None,
+            margin: 32,
+            scale: 0.25,
+            border_width: 0,
+            border_color: "white".to_string(),
+            corner_radius: 0,
+            audio: PipAudio::Primary,
+        }
+    }
+
+    fn inputs() -> Vec<LayoutInput> {
+        vec![
+            LayoutInput { duration: 60.0, has_audio: true, gain: 1.0 },
+            LayoutInput { duration: 45.0, has_audio: true, gain: 0.5 },
+        ]
+    }
+
+    #[test]
+    fn test_pip_graph_corner_preset() {
+        let layout = build_pip_graph(&settings(), 1920, 1080, &inputs());
+
+        assert_eq!(
+            layout.graph,
+            "[0:v]scale=1920:1080:force_original_aspect_ratio=decrease,pad=1920:1080:(ow-iw)/2:(oh-ih)/2:black,setsar=1[base];\
+             [1:v]scale=480:-2,setsar=1[pip];\
+             [base][pip]overlay=x=W-w-32:y=H-h-32:eof_action=pass,format=yuv420p[vout];\
+             [0:a]volume=1[ain0];[ain0]anull[aout]"
+        );
+        assert_eq!(layout.audio_label.as_deref(), Some("[aout]"));
+        assert_eq!(layout.duration, 60.0);
+    }
+
+    #[test]
+    fn test_pip_graph_custom_position_and_styling() {
+        let mut pip = settings();
+        pip.x = Some(100);
+        pip.y = Some(50);
+        pip.border_width = 4;
+        pip.corner_radius = 24;
+        pip.audio = PipAudio::Mix;
+
+        let layout = build_pip_graph(&pip, 1280, 720, &inputs());
+
+        assert!(layout.graph.contains("[1:v]scale=320:-2,setsar=1,pad=iw+8:ih+8:4:4:color=white,format=yuva420p,geq="));
+        assert!(layout.graph.contains("overlay=x=100:y=50:"));
+        assert!(layout.graph.ends_with("[ain0][ain1]amix=inputs=2:duration=first:normalize=0[aout]"));
+    }
+
+    #[test]
+    fn test_pip_graph_without_audio() {
+        let mut pip = settings();
+        pip.audio = PipAudio::Secondary;
+        let mut silent = inputs();
+        silent[1].has_audio = false;
+
+        let layout = build_pip_graph(&pip, 1280, 720, &silent);
+        assert_eq!(layout.audio_label, None);
+        assert!(!layout.graph.contains(":a]"));
+    }
+
+    #[test]
+    fn test_side_by_side_shortest() {
+        let layout = build_stack_graph(StackDirection::Horizontal, DurationSync::Shortest, 1920, 1080, &inputs());
+
+        assert!(layout.graph.starts_with(
+            "[0:v]scale=960:1080:force_original_aspect_ratio=decrease,pad=960:1080:(ow-iw)/2:(oh-ih)/2:black,setsar=1,fps=30[c0];"
+        ));
+        assert!(layout.graph.contains("[c0][c1]hstack=inputs=2:shortest=1,scale=1920:1080"));
+        assert!(layout.graph.contains("amix=inputs=2:duration=shortest:normalize=0[amix]"));
+        assert!(layout.graph.ends_with("[amix]apad=whole_dur=45,atrim=end=45[aout]"));
+        assert!(!layout.graph.contains("tpad"));
+        assert_eq!(layout.duration, 45.0);
+    }
+
+    #[test]
+    fn test_stacked_longest_holds_last_frame() {
+        let layout = build_stack_graph(StackDirection::Vertical, DurationSync::Longest, 1080, 1920, &inputs());
+
+        assert!(layout.graph.contains("[1:v]scale=1080:960:"));
+        assert!(layout.graph.contains("fps=30,tpad=stop_mode=clone:stop_duration=15[c1]"));
+        assert!(layout.graph.contains("vstack=inputs=2:shortest=1"));
+        assert!(layout.graph.contains("duration=longest"));
+        assert_eq!(layout.duration, 60.0);
+    }
+
+    #[test]
+    fn test_grid_positions() {
+        let three: Vec<LayoutInput> = (0..3)
+            .map(|_| LayoutInput { duration: 10.0, has_audio: false, gain: 1.0 })
+            .collect();
+        let layout = build_stack_graph(StackDirection::Grid(2), DurationSync::Shortest, 1920, 1080, &three);
+
+        assert!(layout.graph.contains("xstack=inputs=3:layout=0_0|960_0|0_540:fill=black:shortest=1"));
+        assert_eq!(layout.audio_label, None);
+        assert_eq!(default_grid_columns(4), 2);
+        assert_eq!(default_grid_columns(5), 3);
+    }
+
+    #[test]
+    fn test_layout_input_counts() {
+        let pip = ExportLayout::PictureInPicture(settings());
+        assert!(pip.check_input_count(2).is_ok());
+        assert!(pip.check_input_count(3).is_err());
+
+        let side = ExportLayout::SideBySide(StackSettings::default());
+        assert!(side.check_input_count(4).is_ok());
+        assert!(side.check_input_count(5).is_err());
+
+        let grid = ExportLayout::Grid(GridSettings::default());
+        assert!(grid.check_input_count(9).is_ok());
+        assert!(grid.check_input_count(1).is_err());
+    }
+
+    #[test]
+    fn test_layout_deserializes_with_defaults() {
+        let layout: ExportLayout = serde_json::from_str(r#"{"mode":"grid","columns":3}"#).unwrap();
+        assert_eq!(layout, ExportLayout::Grid(GridSettings { columns: Some(3), sync: DurationSync::Shortest }));
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::detection::{
+        filter_scene_cuts, keep_segments, parse_quality_report, parse_scene_cuts, parse_silencedetect, split_at,
+        Interval, QualityCheckSettings, SceneCut, SceneSettings, SilenceSettings,
+    };
+    use clipforge_lib::utils::ffmpeg::FfmpegBuilder;
+
+    fn interval(start: f64, end: f64) -> Interval {
+        Interval { start, end }
+    }
+
+    #[test]
+    fn test_silence_filter() {
+        assert_eq!(SilenceSettings::default().filter(), "silencedetect=noise=-30dB:d=0.5");
+
+        let settings = SilenceSettings { threshold_db: -120.0, min_duration: 0.0 };
+        assert_eq!(settings.filter(), "silencedetect=noise=-90dB:d=0.01");
+    }
+
+    #[test]
+    fn test_parse_silencedetect() {
+        let stderr = "\
+[silencedetect @ 0x600000] silence_start: 1.504
+[silencedetect @ 0x600000] silence_end: 3.2 | silence_duration: 1.696
+size=N/A time=00:00:05.00 bitrate=N/A speed= 300x
+[silencedetect @ 0x600000] silence_start: -0.01
+[silencedetect @ 0x600000] silence_end: 0.8 | silence_duration: 0.81
+[silencedetect @ 0x600000] silence_start: 8.75
+";
+        assert_eq!(
+            parse_silencedetect(stderr, Some(10.0)),
+            vec![interval(1.504, 3.2), interval(0.0, 0.8), interval(8.75, 10.0)]
+        );
+        assert_eq!(parse_silencedetect(stderr, None).len(), 2);
+    }
+
+    #[test]
+    fn test_keep_segments_with_padding() {
+        let silences = vec![interval(0.0, 1.0), interval(4.0, 6.0), interval(9.5, 10.0)];
+        let keeps = keep_segments(&silences, 0.0, 10.0, 0.2, 0.1);
+
+        assert_eq!(keeps, vec![interval(0.8, 4.2), interval(5.8, 9.7)]);
+    }
+
+    #[test]
+    fn test_keep_segments_drops_short_pieces() {
+        // The 0.05s of speech between the two silences is not worth keeping
+        let silences = vec![interval(1.0, 2.0), interval(2.05, 3.0)];
+        assert_eq!(keep_segments(&silences, 0.0, 5.0, 0.0, 0.1), vec![interval(0.0, 1.0), interval(3.0, 5.0)]);
+
+        assert_eq!(keep_segments(&[], 2.0, 5.0, 0.2, 0.1), vec![interval(2.0, 5.0)]);
+    }
+
+    fn cut(time: f64, score: f64) -> SceneCut {
+        SceneCut { time, score: Some(score) }
+    }
+
+    #[test]
+    fn test_scene_detection_args() {
+        let args = FfmpegBuilder::new()
+            .input("/test/input.mp4")
+            .trim(10.0, 60.0)
+            .video_only()
+            .video_filter(&SceneSettings::default().filter())
+            .analyze()
+            .build_args().unwrap();
+
+        assert_eq!(
+            args.join(" "),
+            "-ss 10 -t 60 -i /test/input.mp4 -vf select='gt(scene,0.3)',metadata=print:key=lavfi.scene_score,showinfo -an -f null -"
+        );
+    }
+
+    #[test]
+    fn test_parse_scene_cuts() {
+        let stderr = "\
+[Parsed_metadata_1 @ 0x6000] frame:0    pts:61440   pts_time:4.8
+[Parsed_metadata_1 @ 0x6000] lavfi.scene_score=0.512300
+[Parsed_showinfo_2 @ 0x6001] n:   0 pts:  61440 pts_time:4.8     duration:512 fmt:yuv420p
+[Parsed_metadata_1 @ 0x6000] frame:1    pts:153600  pts_time:12
+[Parsed_metadata_1 @ 0x6000] lavfi.scene_score=0.9
+[Parsed_showinfo_2 @ 0x6001] n:   1 pts: 153600 pts_time:12      duration:512 fmt:yuv420p
+";
+        assert_eq!(parse_scene_cuts(stderr), vec![cut(4.8, 0.5123), cut(12.0, 0.9)]);
+    }
+
+    #[test]
+    fn test_filter_scene_cuts_and_split() {
+        let cuts = vec![cut(0.4, 0.5), cut(5.0, 0.6), cut(5.5, 0.7), cut(9.8, 0.8)];
+        let kept = filter_scene_cuts(&cuts, 0.0, 10.0, 1.0);
+        assert_eq!(kept, vec![cut(5.0, 0.6)]);
+
+        assert_eq!(
+            split_at(&[7.0, 3.0, 3.0, 12.0], 0.0, 10.0),
+            vec![interval(0.0, 3.0), interval(3.0, 7.0), interval(7.0, 10.0)]
+        );
+        assert_eq!(split_at(&[], 2.0, 4.0), vec![interval(2.0, 4.0)]);
+    }
+
+    #[test]
+    fn test_quality_check_filter() {
+        assert_eq!(
+            QualityCheckSettings::default().filter(),
+            "blackdetect=d=0.5:pix_th=0.1:pic_th=0.98,freezedetect=n=-60dB:d=2"
+        );
+    }
+
+    #[test]
+    fn test_parse_quality_report() {
+        let stderr = "\
+[blackdetect @ 0x6000] black_start:0 black_end:1.5 black_duration:1.5
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_start: 4.004
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_duration: 3.003
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_end: 7.007
+[blackdetect @ 0x6000] black_start:28.2 black_end:30 black_duration:1.8
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_start: 25
+";
+        let report = parse_quality_report(stderr, Some(30.0));
+        assert_eq!(report.black, vec![interval(0.0, 1.5), interval(28.2, 30.0)]);
+        assert_eq!(report.frozen, vec![interval(4.004, 7.007), interval(25.0, 30.0)]);
+
+        let shifted = report.offset(10.0);
+        assert_eq!(shifted.black[0], interval(10.0, 11.5));
+        assert!(!shifted.is_clean());
+        assert!(parse_quality_report("", Some(30.0)).is_clean());
+    }
+}
@@ -1,13 +1,13 @@
 #[cfg(test)]
 mod tests {
-    use clipforge_lib::utils::ffmpeg::{FfmpegBuilder, RawInputConfig, FFmpegError};
+    use clipforge_lib::utils::ffmpeg::{atempo_chain, check_reverse_duration, escape_filter_value, ColorAdjustments, FfmpegBuilder, RawInputConfig, FFmpegError};
 
     #[test]
     fn test_builder_basic_args() {
         let args = FfmpegBuilder::new()
             .input("input.mp4")
             .output("output.mp4")
-            .build_args();
+            .build_args().unwrap();
 
         assert!(args.contains(&"-i".to_string()));
         assert!(args.contains(&"input.mp4".to_string()));
@@ -21,7 +21,7 @@ mod tests {
             .input("input.mp4")
             .trim(1.0, 5.0)
             .output("output.mp4")
-            .build_args();
+            .build_args().unwrap();
 
         let ss_pos = args.iter().position(|s| s == "-ss").unwrap();
         assert_eq!(args[ss_pos + 1], "1");
@@ -35,7 +35,7 @@ mod tests {
             .input("input.mp4")
             .encode()
             .output("output.mp4")
-            .build_args();
+            .build_args().unwrap();
 
         assert!(args.contains(&"-c:v".to_string()));
         assert!(args.contains(&"libx264".to_string()));
@@ -51,7 +51,7 @@ mod tests {
             .input("input.mp4")
             .stream_copy()
             .output("output.mp4")
-            .build_args();
+            .build_args().unwrap();
 
         assert!(args.contains(&"-c".to_string()));
         assert!(args.contains(&"copy".to_string()));
@@ -66,7 +66,7 @@ mod tests {
             .thumbnail(2.5)
             .scale(320, None)
             .output("thumb.jpg")
-            .build_args();
+            .build_args().unwrap();
 
         let ss_pos = args.iter().position(|s| s == "-ss").unwrap();
         assert_eq!(args[ss_pos + 1], "2.5");
@@ -87,7 +87,7 @@ mod tests {
         let args = FfmpegBuilder::new()
             .raw_input(config)
             .output("output.mp4")
-            .build_args();
+            .build_args().unwrap();
 
         assert!(args.contains(&"-f".to_string()));
         assert!(args.contains(&"rawvideo".to_string()));
@@ -105,7 +105,7 @@ mod tests {
             .concat("concat.txt")
             .stream_copy()
             .output("output.mp4")
-            .build_args();
+            .build_args().unwrap();
 
         assert!(args.contains(&"-f".to_string()));
         assert!(args.contains(&"concat".to_string()));
@@ -119,6 +119,254 @@ mod tests {
         assert!(args.contains(&"output.mp4".to_string()));
     }
 
+    #[test]
+    fn test_builder_analysis_args() {
+        let args = FfmpegBuilder::new()
+            .input("input.mp4")
+            .audio_only()
+            .loudnorm("loudnorm=I=-14:TP=-1:LRA=11:print_format=json")
+            .analyze()
+            .output("ignored.mp4")
+            .build_args().unwrap();
+
+        assert!(args.contains(&"-vn".to_string()));
+        let af_pos = args.iter().position(|s| s == "-af").unwrap();
+        assert!(args[af_pos + 1].starts_with("loudnorm="));
+        assert_eq!(&args[args.len() - 3..], &["-f".to_string(), "null".to_string(), "-".to_string()]);
+        assert!(!args.contains(&"ignored.mp4".to_string()));
+    }
+
+    #[test]
+    fn test_builder_copy_video_args() {
+        let args = FfmpegBuilder::new()
+            .input("program.mp4")
+            .copy_video()
+            .volume(0.5)
+            .loudnorm("loudnorm=I=-23")
+            .audio_sample_rate(48000)
+            .output("output.mp4")
+            .build_args().unwrap();
+
+        let cv_pos = args.iter().position(|s| s == "-c:v").unwrap();
+        assert_eq!(args[cv_pos + 1], "copy");
+        let ca_pos = args.iter().position(|s| s == "-c:a").unwrap();
+        assert_eq!(args[ca_pos + 1], "aac");
+        let af_pos = args.iter().position(|s| s == "-af").unwrap();
+        assert_eq!(args[af_pos + 1], "volume=0.5,loudnorm=I=-23");
+        let ar_pos = args.iter().position(|s| s == "-ar").unwrap();
+        assert_eq!(args[ar_pos + 1], "48000");
+    }
+
+    #[test]
+    fn test_builder_gain_and_fades() {
+        let args = FfmpegBuilder::new()
+            .input("input.mp4")
+            .trim(2.0, 10.0)
+            .volume(0.8)
+            .gain_db(6.0)
+            .audio_fade_in(0.5)
+            .audio_fade_out(1.5)
+            .output("output.mp4")
+            .build_args().unwrap();
+
+        let af_pos = args.iter().position(|s| s == "-af").unwrap();
+        assert_eq!(
+            args[af_pos + 1],
+            "volume=0.8,volume=6dB,afade=t=in:st=0:d=0.5,afade=t=out:st=8.5:d=1.5"
+        );
+    }
+
+    #[test]
+    fn test_builder_gain_ceiling() {
+        let args = FfmpegBuilder::new()
+            .input("input.mp4")
+            .gain_db(48.0)
+            .output("output.mp4")
+            .build_args().unwrap();
+
+        assert!(args.contains(&"volume=20dB".to_string()));
+    }
+
+    #[test]
+    fn test_builder_mute_skips_gain_and_fades() {
+        let args = FfmpegBuilder::new()
+            .input("input.mp4")
+            .trim(0.0, 5.0)
+            .gain_db(6.0)
+            .audio_fade_in(1.0)
+            .mute()
+            .output("output.mp4")
+            .build_args().unwrap();
+
+        let af_pos = args.iter().position(|s| s == "-af").unwrap();
+        assert_eq!(args[af_pos + 1], "volume=0");
+    }
+
+    #[test]
+    fn test_builder_filter_complex_args() {
+        let args = FfmpegBuilder::new()
+            .input("program.mp4")
+            .add_input("music.mp3")
+            .add_input_with_options(&["-loop", "1"], "logo.png")
+            .filter_complex("[0:a][1:a]amix=inputs=2[aout]")
+            .map("0:v")
+            .map("[aout]")
+            .copy_video()
+            .output("output.mp4")
+            .build_args().unwrap();
+
+        let inputs: Vec<&String> = args.iter()
+            .enumerate()
+            .filter(|(i, _)| *i > 0 && args[i - 1] == "-i")
+            .map(|(_, s)| s)
+            .collect();
+        assert_eq!(inputs, ["program.mp4", "music.mp3", "logo.png"]);
+
+        let loop_pos = args.iter().position(|s| s == "-loop").unwrap();
+        assert_eq!(args[loop_pos + 2], "-i");
+        let fc_pos = args.iter().position(|s| s == "-filter_complex").unwrap();
+        assert!(fc_pos > loop_pos);
+        assert_eq!(args[fc_pos + 1], "[0:a][1:a]amix=inputs=2[aout]");
+        assert_eq!(&args[fc_pos + 2..fc_pos + 6], &["-map", "0:v", "-map", "[aout]"]);
+
+        assert!(!args.contains(&"-af".to_string()));
+    }
+
+    #[test]
+    fn test_builder_rejects_filters_alongside_filter_complex() {
+        let graph = || FfmpegBuilder::new()
+            .input("program.mp4")
+            .add_input("music.mp3")
+            .filter_complex("[0:a][1:a]amix=inputs=2[aout]")
+            .map("[aout]")
+            .output("output.mp4");
+
+        // FFmpeg would silently ignore -af/-vf next to the graph
+        let audio = graph().volume(0.5).build_args().unwrap_err();
+        assert!(matches!(&audio, FFmpegError::InvalidOptions(msg) if msg.contains("volume=0.5")));
+        let video = graph().scale_with_pad(1280, 720).build_args().unwrap_err();
+        assert!(matches!(video, FFmpegError::InvalidOptions(_)));
+    }
+
+    #[test]
+    fn test_builder_grading_and_video_fades() {
+        let args = FfmpegBuilder::new()
+            .input("input.mp4")
+            .trim(0.0, 8.0)
+            .scale_with_pad(1280, 720)
+            .lut3d("/luts/warm.cube")
+            .color_adjust(ColorAdjustments {
+                brightness: Some(0.05),
+                contrast: Some(1.1),
+                saturation: Some(1.0),
+                gamma: None,
+            })
+            .video_fade_in(1.0)
+            .video_fade_out(2.0)
+            .output("output.mp4")
+            .build_args().unwrap();
+
+        let vf_pos = args.iter().position(|s| s == "-vf").unwrap();
+        let filters: Vec<&str> = args[vf_pos + 1].split(',').collect();
+        assert!(filters[0].starts_with("scale=1280:720"));
+        assert_eq!(filters[2], "lut3d=file='/luts/warm.cube'");
+        assert_eq!(filters[3], "eq=brightness=0.05:contrast=1.1");
+        assert_eq!(filters[4], "fade=t=in:st=0:d=1");
+        assert_eq!(filters[5], "fade=t=out:st=6:d=2");
+    }
+
+    #[test]
+    fn test_color_defaults_produce_no_filter() {
+        let color = ColorAdjustments { brightness: Some(0.0), contrast: None, saturation: Some(1.0), gamma: Some(1.0) };
+        assert_eq!(color.eq_filter(), None);
+    }
+
+    #[test]
+    fn test_escape_filter_value() {
+        assert_eq!(escape_filter_value("C:\\luts\\film.cube"), "'C\\:/luts/film.cube'");
+        assert_eq!(escape_filter_value("/tmp/it's.cube"), "'/tmp/it'\\\\\\''s.cube'");
+    }
+
+    #[test]
+    fn test_builder_speed_and_reverse() {
+        let builder = FfmpegBuilder::new()
+            .input("input.mp4")
+            .trim(0.0, 10.0)
+            .speed(2.0)
+            .reverse()
+            .video_fade_out(1.0)
+            .audio_fade_out(1.0)
+            .output("output.mp4");
+        assert_eq!(builder.output_duration(), Some(5.0));
+
+        let args = builder.build_args().unwrap();
+        let vf_pos = args.iter().position(|s| s == "-vf").unwrap();
+        assert_eq!(args[vf_pos + 1], "reverse,setpts=(PTS-STARTPTS)/2,fade=t=out:st=4:d=1");
+        let af_pos = args.iter().position(|s| s == "-af").unwrap();
+        assert_eq!(args[af_pos + 1], "areverse,atempo=2,afade=t=out:st=4:d=1");
+    }
+
+    #[test]
+    fn test_reverse_duration_limit() {
+        assert!(check_reverse_duration(60.0).is_ok());
+        assert!(check_reverse_duration(600.0).unwrap_err().contains("limited to 120s"));
+    }
+
+    #[test]
+    fn test_atempo_chain() {
+        assert_eq!(atempo_chain(1.0), Vec::<String>::new());
+        assert_eq!(atempo_chain(1.5), vec!["atempo=1.5"]);
+        assert_eq!(atempo_chain(4.0), vec!["atempo=2", "atempo=2"]);
+        assert_eq!(atempo_chain(3.0), vec!["atempo=2", "atempo=1.5"]);
+        assert_eq!(atempo_chain(0.25), vec!["atempo=0.5", "atempo=0.5"]);
+        assert_eq!(atempo_chain(0.1), vec!["atempo=0.5", "atempo=0.5"]);
+        assert_eq!(atempo_chain(8.0), vec!["atempo=2", "atempo=2"]);
+    }
+
+    #[test]
+    fn test_burn_subtitles_shifts_to_source_time() {
+        let args = FfmpegBuilder::new()
+            .input("/test/input.mp4")
+            .output("/test/output.mp4")
+            .trim(12.5, 10.0)
+            .scale_with_pad(1280, 720)
+            .burn_subtitles("subtitles=filename='/test/captions.srt'")
+            .speed(2.0)
+            .build_args().unwrap();
+
+        let vf = &args[args.iter().position(|a| a == "-vf").unwrap() + 1];
+        assert!(vf.contains(
+            "black,setpts=PTS+12.5/TB,subtitles=filename='/test/captions.srt',setpts=PTS-STARTPTS,setpts=(PTS-STARTPTS)/2"
+        ));
+
+        // No shift needed without a trim offset
+        let args = FfmpegBuilder::new()
+            .input("/test/input.mp4")
+            .output("/test/output.mp4")
+            .burn_subtitles("subtitles=filename='/test/captions.vtt'")
+            .build_args().unwrap();
+        assert!(args.contains(&"subtitles=filename='/test/captions.vtt'".to_string()));
+    }
+
+    #[test]
+    fn test_subtitle_stream_mux() {
+        let args = FfmpegBuilder::new()
+            .input("/test/output.mp4")
+            .add_input("/test/captions.srt")
+            .map("0:v")
+            .map("0:a?")
+            .map("1:0")
+            .stream_copy()
+            .subtitle_codec("mov_text")
+            .stream_metadata("s:s:0", "language=eng")
+            .output("/test/muxed.mp4")
+            .build_args().unwrap();
+
+        let joined = args.join(" ");
+        assert!(joined.contains("-i /test/captions.srt -map 0:v -map 0:a? -map 1:0 -c copy"));
+        assert!(joined.contains("-c:s mov_text -metadata:s:s:0 language=eng -y /test/muxed.mp4"));
+    }
+
     #[test]
     fn test_error_display() {
         let error = FFmpegError::CommandSpawn("test error".to_string());
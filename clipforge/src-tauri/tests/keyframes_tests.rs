@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::keyframes::{cache_file_name, KeyframeIndex, KeyframeNeighbors};
+
+    const PACKETS: &str = "\
+0.000000,0.033333,K__
+0.033333,0.033333,___
+2.002000,0.033333,K__
+N/A,0.033333,___
+1.968667,0.033333,___
+4.004000,0.033333,K_D
+4.037333,0.033333,___
+";
+
+    fn index() -> KeyframeIndex {
+        KeyframeIndex::from_packets("/clips/a.mp4", (1024, 1700000000), PACKETS)
+    }
+
+    #[test]
+    fn test_index_from_packets() {
+        let index = index();
+        assert_eq!(index.keyframes, vec![0.0, 2.002, 4.004]);
+        assert_eq!(index.packet_count, 6);
+        assert!((index.duration - 4.070666).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_index_freshness() {
+        let index = index();
+        assert!(index.is_fresh("/clips/a.mp4", (1024, 1700000000)));
+        assert!(!index.is_fresh("/clips/a.mp4", (2048, 1700000000)));
+        assert!(!index.is_fresh("/clips/a.mp4", (1024, 1700000500)));
+        assert!(!index.is_fresh("/clips/b.mp4", (1024, 1700000000)));
+    }
+
+    #[test]
+    fn test_index_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(cache_file_name("/clips/a.mp4"));
+        let index = index();
+        index.write_cache(&path).unwrap();
+
+        assert_eq!(KeyframeIndex::read_cache(&path, "/clips/a.mp4", (1024, 1700000000)), Some(index));
+        assert_eq!(KeyframeIndex::read_cache(&path, "/clips/a.mp4", (1, 1)), None);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(KeyframeIndex::read_cache(&path, "/clips/a.mp4", (1024, 1700000000)), None);
+    }
+
+    #[test]
+    fn test_cache_file_name_includes_path_hash() {
+        let name = cache_file_name("/clips/a.mp4");
+        assert!(name.starts_with("a.mp4_") && name.ends_with("_keyframes.json"));
+        assert_eq!(name, cache_file_name("/clips/a.mp4"));
+        assert_ne!(name, cache_file_name("/other/a.mp4"));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let index = index();
+        assert_eq!(
+            index.neighbors(1.5),
+            KeyframeNeighbors { before: Some(0.0), after: Some(2.002), nearest: Some(2.002) }
+        );
+        assert_eq!(
+            index.neighbors(2.002),
+            KeyframeNeighbors { before: Some(2.002), after: Some(2.002), nearest: Some(2.002) }
+        );
+        assert_eq!(
+            index.neighbors(9.0),
+            KeyframeNeighbors { before: Some(4.004), after: None, nearest: Some(4.004) }
+        );
+        assert_eq!(index.neighbors(0.9).nearest, Some(0.0));
+    }
+}
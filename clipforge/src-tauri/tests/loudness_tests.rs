@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::loudness::{parse_loudnorm_output, LoudnessMeasurement, LoudnessTarget};
+
+    const LOUDNORM_STDERR: &str = r#"size=N/A time=00:00:10.00 bitrate=N/A speed= 120x
+[Parsed_loudnorm_0 @ 0x600000b2c000]
+{
+	"input_i" : "-27.61",
+	"input_tp" : "-4.47",
+	"input_lra" : "18.06",
+	"input_thresh" : "-39.20",
+	"output_i" : "-16.58",
+	"output_tp" : "-1.50",
+	"output_lra" : "14.78",
+	"output_thresh" : "-27.71",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.58"
+}
+"#;
+
+    #[test]
+    fn test_parse_loudnorm_output() {
+        let measured = parse_loudnorm_output(LOUDNORM_STDERR).unwrap();
+        assert_eq!(measured, LoudnessMeasurement {
+            integrated: -27.61,
+            true_peak: -4.47,
+            lra: 18.06,
+            threshold: -39.20,
+            target_offset: 0.58,
+        });
+        assert!(measured.is_measurable());
+    }
+
+    #[test]
+    fn test_parse_loudnorm_silence() {
+        let stderr = LOUDNORM_STDERR
+            .replace("\"-27.61\"", "\"-inf\"")
+            .replace("\"-4.47\"", "\"-inf\"");
+        let measured = parse_loudnorm_output(&stderr).unwrap();
+        assert!(!measured.is_measurable());
+    }
+
+    #[test]
+    fn test_parse_loudnorm_missing() {
+        assert!(parse_loudnorm_output("No audio stream").is_none());
+    }
+
+    #[test]
+    fn test_normalize_filter_uses_measurement() {
+        let measured = parse_loudnorm_output(LOUDNORM_STDERR).unwrap();
+        let filter = LoudnessTarget::broadcast().normalize_filter(&measured);
+
+        assert!(filter.starts_with("loudnorm=I=-23:TP=-1:LRA=7"));
+        assert!(filter.contains("measured_I=-27.61"));
+        assert!(filter.contains("measured_thresh=-39.20"));
+        assert!(filter.contains("offset=0.58"));
+        assert!(filter.contains("linear=true"));
+    }
+
+    #[test]
+    fn test_target_clamped_to_filter_range() {
+        let target = LoudnessTarget { integrated: 0.0, true_peak: 3.0, lra: 100.0 };
+        assert_eq!(target.measure_filter(), "loudnorm=I=-5:TP=0:LRA=50:print_format=json");
+    }
+}
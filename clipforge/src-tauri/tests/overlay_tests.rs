@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::overlay::{even_dimension, Anchor, Watermark};
+
+    fn watermark() -> Watermark {
+        Watermark {
+            path: "logo.png".to_string(),
+            anchor: Anchor::BottomRight,
+            margin: 24,
+            scale: 0.15,
+            opacity: 1.0,
+            start: None,
+            end: None,
+        }
+    }
+
+    #[test]
+    fn test_watermark_overlay_graph() {
+        assert_eq!(
+            watermark().overlay_graph(2, "[0:v]", "[vout]", 1920),
+            "[2:v]format=rgba,scale=288:-1[wm];[0:v][wm]overlay=x=W-w-24:y=H-h-24[vout]"
+        );
+    }
+
+    #[test]
+    fn test_watermark_opacity_and_range() {
+        let mut wm = watermark();
+        wm.anchor = Anchor::TopLeft;
+        wm.opacity = 0.6;
+        wm.start = Some(5.0);
+
+        let graph = wm.overlay_graph(1, "[vtext]", "[vout]", 1280);
+        assert!(graph.starts_with("[1:v]format=rgba,scale=192:-1,colorchannelmixer=aa=0.6[wm]"));
+        assert!(graph.ends_with("[vtext][wm]overlay=x=24:y=24:enable='gte(t,5)'[vout]"));
+    }
+
+    #[test]
+    fn test_even_dimension() {
+        assert_eq!(even_dimension(191.9), 190);
+        assert_eq!(even_dimension(0.5), 2);
+        assert_eq!(even_dimension(320.0), 320);
+    }
+}
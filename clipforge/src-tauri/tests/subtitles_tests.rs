@@ -0,0 +1,270 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::subtitles::{
+        ass_color, format_timestamp, is_supported_caption_file, merge_cues, merge_with_next, parse_ass, parse_srt,
+        parse_timestamp, parse_vtt, read_caption_file, retime_cues, rewrap_cues, shift_cues, split_cue,
+        strip_timestamp_tags, wrap_text, write_ass, write_caption_file, write_edited_captions, write_srt, write_vtt,
+        CaptionFormat, CaptionPosition, CaptionStyle, Cue, CueTiming, EmbeddedCaptions, MkvSubtitleCodec,
+    };
+
+    fn cue(start: f64, end: f64, text: &str) -> Cue {
+        Cue { start, end, text: text.to_string() }
+    }
+
+    fn timing(trim_start: f64, trim_end: f64, offset: f64) -> CueTiming {
+        CueTiming { trim_start, trim_end, speed: 1.0, reverse: false, offset }
+    }
+
+    #[test]
+    fn test_ass_color() {
+        assert_eq!(ass_color("#FFCC00"), Some("&H0000CCFF".to_string()));
+        assert_eq!(ass_color("#ffffff"), Some("&H00FFFFFF".to_string()));
+        assert_eq!(ass_color("FFFFFF"), None);
+        assert_eq!(ass_color("#FFF"), None);
+        assert_eq!(ass_color("#GGGGGG"), None);
+    }
+
+    #[test]
+    fn test_supported_caption_files() {
+        assert!(is_supported_caption_file("/captions/clip.vtt"));
+        assert!(is_supported_caption_file("/captions/clip.SRT"));
+        assert!(is_supported_caption_file("/captions/clip.ass"));
+        assert!(is_supported_caption_file("/captions/clip.ssa"));
+        assert!(!is_supported_caption_file("/captions/clip.sub"));
+        assert!(!is_supported_caption_file("/captions/clip"));
+    }
+
+    #[test]
+    fn test_subtitles_filter_with_style() {
+        let style = CaptionStyle {
+            font_name: Some("Helvetica Neue".to_string()),
+            font_size: 24,
+            color: "#FFCC00".to_string(),
+            outline: 2.0,
+            bold: true,
+            position: CaptionPosition::Top,
+            margin: 30,
+            ..CaptionStyle::default()
+        };
+
+        assert_eq!(
+            style.subtitles_filter("C:\\captions\\clip.srt"),
+            "subtitles=filename='C\\:/captions/clip.srt':force_style='FontName=Helvetica Neue,FontSize=24,\
+             PrimaryColour=&H0000CCFF,OutlineColour=&H00000000,BorderStyle=1,Outline=2,Bold=-1,Alignment=8,MarginV=30'"
+        );
+    }
+
+    #[test]
+    fn test_style_validation() {
+        assert!(CaptionStyle::default().validate().is_ok());
+
+        let bad_color = CaptionStyle { color: "white".to_string(), ..CaptionStyle::default() };
+        assert!(bad_color.validate().is_err());
+
+        let bad_font = CaptionStyle { font_name: Some("Arial,Bold".to_string()), ..CaptionStyle::default() };
+        assert!(bad_font.validate().is_err());
+    }
+
+    #[test]
+    fn test_timestamps() {
+        assert_eq!(parse_timestamp("00:01:02.500"), Some(62.5));
+        assert_eq!(parse_timestamp("01:02.250"), Some(62.25));
+        assert_eq!(parse_timestamp("01:00:00,040"), Some(3600.04));
+        assert_eq!(parse_timestamp("00:61:00.000"), None);
+        assert_eq!(parse_timestamp("garbage"), None);
+        assert_eq!(format_timestamp(3723.4567, '.'), "01:02:03.457");
+        assert_eq!(format_timestamp(5.0, ','), "00:00:05,000");
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let content = "\u{feff}WEBVTT\r\n\r\nNOTE generated\r\n\r\nintro\r\n00:00.000 --> 00:02.000 align:start\r\nHello\r\nworld\r\n\r\n00:00:03.000 --> 00:00:04.500\r\nSecond\r\n";
+        let cues = parse_vtt(content).unwrap();
+
+        assert_eq!(cues, vec![cue(0.0, 2.0, "Hello\nworld"), cue(3.0, 4.5, "Second")]);
+        assert!(parse_vtt("1\n00:00:01,000 --> 00:00:02,000\nHi\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let content = "1\n00:00:01,000 --> 00:00:02,000\nHi\n\n2\n00:00:03,000 --> 00:00:04,000\nThere\n";
+        assert_eq!(parse_srt(content).unwrap(), vec![cue(1.0, 2.0, "Hi"), cue(3.0, 4.0, "There")]);
+        assert!(parse_srt("not captions").is_err());
+    }
+
+    #[test]
+    fn test_retime_cues_clips_to_trim() {
+        let cues = vec![cue(1.0, 3.0, "before"), cue(4.0, 6.0, "edge"), cue(7.0, 8.0, "inside"), cue(12.0, 14.0, "after")];
+        let retimed = retime_cues(&cues, &timing(5.0, 10.0, 20.0));
+
+        assert_eq!(retimed, vec![cue(20.0, 21.0, "edge"), cue(22.0, 23.0, "inside")]);
+    }
+
+    #[test]
+    fn test_word_timestamps_dropped_when_retimed() {
+        let tagged = "Hello <00:00:04.500>big <4:05.000>world <b>now</b> a<b";
+        assert_eq!(strip_timestamp_tags(tagged), "Hello big world <b>now</b> a<b");
+
+        let cues = vec![cue(4.0, 6.0, "Hello <00:00:04.500>world")];
+        assert_eq!(retime_cues(&cues, &timing(0.0, 10.0, 20.0)), vec![cue(24.0, 26.0, "Hello world")]);
+        assert_eq!(shift_cues(&cues, 1.0)[0].text, "Hello world");
+        assert!(write_srt(&cues).contains("\nHello world\n"));
+    }
+
+    #[test]
+    fn test_retime_cues_speed_and_reverse() {
+        let cues = vec![cue(2.0, 4.0, "a")];
+
+        let fast = CueTiming { speed: 2.0, ..timing(0.0, 10.0, 0.0) };
+        assert_eq!(retime_cues(&cues, &fast), vec![cue(1.0, 2.0, "a")]);
+
+        let reversed = CueTiming { reverse: true, ..timing(0.0, 10.0, 0.0) };
+        assert_eq!(retime_cues(&cues, &reversed), vec![cue(6.0, 8.0, "a")]);
+    }
+
+    #[test]
+    fn test_merge_and_write() {
+        let merged = merge_cues(vec![vec![cue(10.0, 12.0, "second")], vec![cue(0.0, 1.0, "first"), cue(14.0, 16.0, "late")]], 15.0);
+        assert_eq!(merged, vec![cue(0.0, 1.0, "first"), cue(10.0, 12.0, "second"), cue(14.0, 15.0, "late")]);
+
+        assert_eq!(
+            write_vtt(&merged[..2]),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.000\nfirst\n\n00:00:10.000 --> 00:00:12.000\nsecond\n"
+        );
+        assert_eq!(
+            write_srt(&merged[..2]),
+            "1\n00:00:00,000 --> 00:00:01,000\nfirst\n\n2\n00:00:10,000 --> 00:00:12,000\nsecond\n"
+        );
+    }
+
+    #[test]
+    fn test_embedded_caption_codecs() {
+        let mut settings = EmbeddedCaptions { language: "eng".to_string(), title: Some("English".to_string()), mkv_codec: MkvSubtitleCodec::Srt };
+        assert_eq!(settings.codec_for("/out/video.MP4"), Ok("mov_text"));
+        assert_eq!(settings.codec_for("/out/video.mov"), Ok("mov_text"));
+        assert_eq!(settings.codec_for("/out/video.mkv"), Ok("srt"));
+        assert!(settings.codec_for("/out/video.webm").is_err());
+        assert!(settings.codec_for("/out/video.avi").is_err());
+
+        settings.mkv_codec = MkvSubtitleCodec::Ass;
+        assert_eq!(settings.codec_for("/out/video.mkv"), Ok("ass"));
+        assert_eq!(settings.metadata(), vec!["language=eng", "title=English"]);
+    }
+
+    #[test]
+    fn test_embedded_caption_language() {
+        let settings: EmbeddedCaptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.language, "eng");
+        assert!(settings.validate().is_ok());
+
+        let invalid = EmbeddedCaptions { language: "en-US".to_string(), ..settings };
+        assert!(invalid.validate().is_err());
+    }
+
+    fn sample() -> Vec<Cue> {
+        vec![
+            cue(0.5, 2.25, "Hello, world"),
+            cue(3.0, 5.0, "Two lines\nof text"),
+            cue(3725.04, 3727.5, "Over an hour in"),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_all_formats() {
+        let cues = sample();
+        assert_eq!(parse_vtt(&write_vtt(&cues)).unwrap(), cues);
+        assert_eq!(parse_srt(&write_srt(&cues)).unwrap(), cues);
+        assert_eq!(parse_ass(&write_ass(&cues)).unwrap(), cues);
+    }
+
+    #[test]
+    fn test_parse_ass_strips_styling() {
+        let content = "[Script Info]\nTitle: test\n\n[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+                       Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,{\\i1}Second{\\i0}, with comma\n\
+                       Comment: 0,0:00:00.00,0:00:01.00,Default,,0,0,0,,ignored\n\
+                       Dialogue: 0,0:00:00.50,0:00:01.00,Default,,0,0,0,,First\\Nline\n";
+        assert_eq!(
+            parse_ass(content).unwrap(),
+            vec![cue(0.5, 1.0, "First\nline"), cue(2.0, 3.0, "Second, with comma")]
+        );
+        assert!(parse_ass("WEBVTT\n").is_err());
+    }
+
+    #[test]
+    fn test_write_ass_escapes_braces() {
+        let cues = vec![cue(0.0, 1.0, "Use {braces} here")];
+        let content = write_ass(&cues);
+        assert!(content.contains(",,Use \\{braces\\} here\n"));
+        assert_eq!(parse_ass(&content).unwrap(), cues);
+    }
+
+    #[test]
+    fn test_edited_captions_never_overwrite_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("captions.ass");
+        let edited = dir.path().join("captions.edited.ass");
+        std::fs::write(&source, write_ass(&sample())).unwrap();
+        let source = source.to_str().unwrap();
+        let shifted = shift_cues(&sample(), 1.0);
+
+        assert!(write_edited_captions(source, source, &shifted).is_err());
+        let aliased = format!("{}/./captions.ass", dir.path().display());
+        assert!(write_edited_captions(source, &aliased, &shifted).is_err());
+
+        write_edited_captions(source, edited.to_str().unwrap(), &shifted).unwrap();
+        assert_eq!(read_caption_file(source).unwrap(), sample());
+        assert_eq!(read_caption_file(edited.to_str().unwrap()).unwrap(), shifted);
+    }
+
+    #[test]
+    fn test_convert_between_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let vtt = dir.path().join("captions.vtt");
+        let srt = dir.path().join("captions.srt");
+        std::fs::write(&vtt, write_vtt(&sample())).unwrap();
+
+        let cues = read_caption_file(vtt.to_str().unwrap()).unwrap();
+        write_caption_file(srt.to_str().unwrap(), &cues).unwrap();
+        assert_eq!(read_caption_file(srt.to_str().unwrap()).unwrap(), sample());
+
+        assert_eq!(CaptionFormat::from_path("/a/b.SSA"), Some(CaptionFormat::Ass));
+        assert!(write_caption_file(dir.path().join("captions.txt").to_str().unwrap(), &cues).is_err());
+    }
+
+    #[test]
+    fn test_shift_cues() {
+        let cues = vec![cue(1.0, 2.0, "a"), cue(3.0, 4.0, "b")];
+        assert_eq!(shift_cues(&cues, 1.5), vec![cue(2.5, 3.5, "a"), cue(4.5, 5.5, "b")]);
+        assert_eq!(shift_cues(&cues, -3.5), vec![cue(0.0, 0.5, "b")]);
+    }
+
+    #[test]
+    fn test_split_cue() {
+        let cues = vec![cue(0.0, 4.0, "one two three four")];
+        let split = split_cue(&cues, 0, 1.0).unwrap();
+        assert_eq!(split, vec![cue(0.0, 1.0, "one"), cue(1.0, 4.0, "two three four")]);
+
+        let single = split_cue(&[cue(0.0, 2.0, "word")], 0, 1.0).unwrap();
+        assert_eq!(single, vec![cue(0.0, 1.0, "word"), cue(1.0, 2.0, "word")]);
+
+        assert!(split_cue(&cues, 0, 4.0).is_err());
+        assert!(split_cue(&cues, 1, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_merge_with_next() {
+        let cues = vec![cue(0.0, 1.0, "a"), cue(1.5, 2.0, "b"), cue(3.0, 4.0, "c")];
+        assert_eq!(merge_with_next(&cues, 0).unwrap(), vec![cue(0.0, 2.0, "a\nb"), cue(3.0, 4.0, "c")]);
+        assert!(merge_with_next(&cues, 2).is_err());
+    }
+
+    #[test]
+    fn test_rewrap() {
+        assert_eq!(wrap_text("the quick brown fox\njumps over", 10), "the quick\nbrown fox\njumps over");
+        assert_eq!(wrap_text("extraordinarily long", 5), "extraordinarily\nlong");
+
+        let wrapped = rewrap_cues(&[cue(0.0, 1.0, "a b c d")], 3).unwrap();
+        assert_eq!(wrapped[0].text, "a b\nc d");
+        assert!(rewrap_cues(&[], 0).is_err());
+    }
+}
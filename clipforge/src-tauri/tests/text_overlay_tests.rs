@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::ffmpeg::FfmpegBuilder;
+    use clipforge_lib::utils::text_overlay::{
+        color_source, escape_drawtext_text, is_valid_color, is_valid_position_expression, TextOverlay, TextPosition, TitleCard,
+    };
+
+    #[test]
+    fn test_lower_third_drawtext() {
+        let mut overlay = TextOverlay::centered("Jane Doe, Host", Some("/fonts/Inter.ttf".to_string()), 36, "white");
+        overlay.position = TextPosition::LowerThird;
+        overlay.box_color = Some("black@0.6".to_string());
+        overlay.start = Some(2.0);
+        overlay.end = Some(6.5);
+
+        assert_eq!(
+            overlay.drawtext_filter(),
+            "drawtext=fontfile='/fonts/Inter.ttf':text='Jane Doe, Host':expansion=none:fontsize=36:fontcolor=white:\
+             box=1:boxcolor=black@0.6:boxborderw=12:x='40':y='h*2/3':enable='between(t,2,6.5)'"
+        );
+    }
+
+    #[test]
+    fn test_anchor_positions() {
+        let mut overlay = TextOverlay::centered("Hi", None, 48, "white");
+        overlay.position = TextPosition::BottomRight;
+        overlay.margin = 20;
+        let filter = overlay.drawtext_filter();
+        assert!(filter.contains("x='w-text_w-20':y='h-text_h-20'"));
+        assert!(!filter.contains("enable"));
+
+        overlay.position = TextPosition::Custom;
+        overlay.x = Some("if(gt(t,1),100,50)".to_string());
+        overlay.y = None;
+        assert!(overlay.drawtext_filter().contains("x='if(gt(t,1),100,50)':y='0'"));
+    }
+
+    #[test]
+    fn test_escape_drawtext_text() {
+        assert_eq!(escape_drawtext_text("Time: 10:30"), "'Time\\: 10\\:30'");
+        assert_eq!(escape_drawtext_text("It's"), "'It'\\\\\\''s'");
+    }
+
+    #[test]
+    fn test_validate_filter_values() {
+        assert!(is_valid_color("white"));
+        assert!(is_valid_color("#FFCC00@0.9"));
+        assert!(is_valid_color("0x000000cc"));
+        assert!(!is_valid_color("red:fontsize=400"));
+        assert!(!is_valid_color("#FFF"));
+        assert!(!is_valid_color("black@2"));
+
+        assert!(is_valid_position_expression("if(gt(t,1),100,50)"));
+        assert!(!is_valid_position_expression("10':fontcolor=red:x='0"));
+        assert!(!is_valid_position_expression("w;[v]null"));
+
+        let mut overlay = TextOverlay::centered("Hi", None, 48, "white");
+        overlay.box_color = Some("black,drawbox".to_string());
+        assert!(overlay.validate().is_err());
+
+        let card = TitleCard {
+            text: "Chapter 1".to_string(),
+            background: "navy:s=10x10".to_string(),
+            font_file: None,
+            font_size: 72,
+            color: "white".to_string(),
+        };
+        assert!(card.validate().is_err());
+    }
+
+    #[test]
+    fn test_title_card_builder() {
+        let card = TitleCard {
+            text: "Chapter 1".to_string(),
+            background: "navy".to_string(),
+            font_file: None,
+            font_size: 72,
+            color: "white".to_string(),
+        };
+
+        let args = FfmpegBuilder::new()
+            .input_format("lavfi")
+            .input(&card.color_source(1280, 720, 3.0))
+            .trim(0.0, 3.0)
+            .text_overlay(card.text_overlay())
+            .shortest()
+            .output("title.mp4")
+            .build_args().unwrap();
+
+        let i_pos = args.iter().position(|s| s == "-i").unwrap();
+        assert_eq!(&args[i_pos - 2..i_pos], &["-f", "lavfi"]);
+        assert_eq!(args[i_pos + 1], "color=c=navy:s=1280x720:d=3:r=30");
+        let vf_pos = args.iter().position(|s| s == "-vf").unwrap();
+        assert!(args[vf_pos + 1].starts_with("drawtext=text='Chapter 1'"));
+        assert!(args.contains(&"-shortest".to_string()));
+    }
+
+    #[test]
+    fn test_gap_source_has_no_text() {
+        let args = FfmpegBuilder::new()
+            .input_format("lavfi")
+            .input(&color_source("black", 1920, 1080, 2.5))
+            .trim(0.0, 2.5)
+            .output("gap.mp4")
+            .build_args().unwrap();
+
+        assert!(args.contains(&"color=c=black:s=1920x1080:d=2.5:r=30".to_string()));
+        assert!(!args.contains(&"-vf".to_string()));
+    }
+}
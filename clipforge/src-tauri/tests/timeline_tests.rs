@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::clip::Clip;
+    use clipforge_lib::utils::timeline::{
+        build_layer_graph, remap_transitions, Gap, IssueKind, SequenceItem, Timeline, TrackItem, TrackLayer,
+    };
+    use clipforge_lib::utils::transitions::{Transition, TransitionKind};
+
+    fn clip(id: &str, track: u32, start: f64, end: f64) -> Clip {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": format!("{}.mp4", id),
+            "path": format!("/clips/{}.mp4", id),
+            "start": start,
+            "end": end,
+            "duration": 30.0,
+            "track": track,
+            "trimStart": 0.0,
+            "trimEnd": end - start,
+        }))
+        .unwrap()
+    }
+
+    fn media(_path: &str) -> Option<f64> {
+        Some(30.0)
+    }
+
+    fn transition(after_clip: usize) -> Transition {
+        Transition { after_clip, kind: TransitionKind::Crossfade, duration: 0.5 }
+    }
+
+    #[test]
+    fn test_from_clips_groups_tracks_and_fills_gaps() {
+        let timeline = Timeline::from_clips(vec![
+            clip("c", 1, 2.0, 4.0),
+            clip("b", 0, 8.0, 10.0),
+            clip("a", 0, 0.0, 5.0),
+        ]);
+
+        assert_eq!(timeline.tracks.len(), 2);
+        let track0 = &timeline.tracks[0];
+        assert_eq!(track0.index, 0);
+        assert!(matches!(&track0.items[0], TrackItem::Clip(c) if c.id == "a"));
+        assert_eq!(track0.items[1], TrackItem::Gap(Gap { start: 5.0, end: 8.0 }));
+        assert!(matches!(&track0.items[2], TrackItem::Clip(c) if c.id == "b"));
+
+        let track1 = &timeline.tracks[1];
+        assert_eq!(track1.items[0], TrackItem::Gap(Gap { start: 0.0, end: 2.0 }));
+        assert_eq!(timeline.duration(), 10.0);
+    }
+
+    #[test]
+    fn test_valid_timeline_has_no_issues() {
+        let timeline = Timeline::from_clips(vec![clip("a", 0, 0.0, 5.0), clip("b", 0, 5.0, 9.0)]);
+        assert!(timeline.validate(media).is_empty());
+    }
+
+    #[test]
+    fn test_validate_overlap() {
+        let timeline = Timeline::from_clips(vec![clip("a", 0, 0.0, 5.0), clip("b", 0, 4.0, 6.0), clip("c", 1, 4.0, 6.0)]);
+        let issues = timeline.validate(media);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].clip_id, "b");
+        assert_eq!(issues[0].kind, IssueKind::Overlap);
+    }
+
+    #[test]
+    fn test_stacked_tracks_are_valid() {
+        // A clip on track 1 plays over track 0 rather than conflicting with it
+        let timeline = Timeline::from_clips(vec![clip("a", 0, 0.0, 10.0), clip("b", 1, 3.0, 8.0)]);
+        assert!(timeline.validate(media).is_empty());
+    }
+
+    #[test]
+    fn test_validate_media_and_trims() {
+        let mut over_trimmed = clip("b", 0, 5.0, 9.0);
+        over_trimmed.trim_start = 28.0;
+        over_trimmed.trim_end = 32.0;
+        let mut misplaced = clip("c", 0, 9.0, 20.0);
+        misplaced.trim_end = 2.0;
+
+        let timeline = Timeline::from_clips(vec![clip("a", 0, 0.0, 5.0), over_trimmed, misplaced]);
+        let issues = timeline.validate(|path| (path != "/clips/a.mp4").then_some(30.0));
+        let kinds: Vec<(&str, IssueKind)> = issues.iter().map(|i| (i.clip_id.as_str(), i.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("a", IssueKind::MissingMedia),
+                ("b", IssueKind::TrimOutOfRange),
+                ("c", IssueKind::InvalidPlacement),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_trim_allows_short_final_frame() {
+        // A stream-copied source can probe a fraction of a frame shorter than its trim
+        let timeline = Timeline::from_clips(vec![clip("a", 0, 0.0, 30.0)]);
+        assert!(timeline.validate(|_| Some(29.98)).is_empty());
+
+        let issues = timeline.validate(|_| Some(29.9));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::TrimOutOfRange);
+    }
+
+    #[test]
+    fn test_track_sequence_pads_to_timeline_end() {
+        let timeline = Timeline::from_clips(vec![
+            clip("a", 0, 1.0, 5.0),
+            clip("b", 0, 5.02, 7.0),
+            clip("c", 1, 9.0, 10.0),
+        ]);
+        let ids = |track: usize| -> Vec<String> {
+            timeline.tracks[track]
+                .sequence(timeline.duration())
+                .iter()
+                .map(|item| match item {
+                    SequenceItem::Clip(c) => c.id.clone(),
+                    SequenceItem::Gap(d) => format!("gap {}", d),
+                })
+                .collect()
+        };
+        // The 0.02s gap between a and b is below the tolerance
+        assert_eq!(ids(0), vec!["gap 1", "a", "b", "gap 3"]);
+        assert_eq!(ids(1), vec!["gap 9", "c"]);
+        assert_eq!(timeline.tracks[0].spans(), vec![(1.0, 5.0), (5.02, 7.0)]);
+    }
+
+    #[test]
+    fn test_remap_transitions() {
+        let timeline = Timeline::from_clips(vec![
+            clip("a", 0, 0.0, 5.0),
+            clip("b", 0, 5.0, 8.0),
+            clip("c", 0, 10.0, 12.0),
+        ]);
+        let sequence = timeline.tracks[0].sequence(timeline.duration());
+
+        assert_eq!(remap_transitions(&sequence, &[transition(0)]).unwrap(), vec![transition(0)]);
+        assert!(remap_transitions(&sequence, &[transition(1)]).is_err());
+        assert!(remap_transitions(&sequence, &[transition(2)]).is_err());
+
+        let offset = Timeline::from_clips(vec![clip("a", 0, 2.0, 5.0), clip("b", 0, 5.0, 8.0)]);
+        assert_eq!(remap_transitions(&offset.tracks[0].sequence(offset.duration()), &[transition(0)]).unwrap(), vec![transition(1)]);
+    }
+
+    #[test]
+    fn test_layer_graph_overlays_upper_tracks_while_they_have_clips() {
+        let layers = [
+            TrackLayer { spans: vec![(0.0, 10.0)], has_audio: true },
+            TrackLayer { spans: vec![(2.0, 4.0), (6.0, 8.0)], has_audio: false },
+            TrackLayer { spans: vec![(5.0, 6.0)], has_audio: true },
+        ];
+        let (graph, has_audio) = build_layer_graph(&layers);
+
+        assert!(graph.contains("[0:v][1:v]overlay=eof_action=pass:enable='between(t,2,4)+between(t,6,8)'[layer1]"));
+        assert!(graph.contains("[layer1][2:v]overlay=eof_action=pass:enable='between(t,5,6)'[layer2]"));
+        assert!(graph.contains("[layer2]format=yuv420p[vout]"));
+        assert!(graph.contains("[0:a][2:a]amix=inputs=2:duration=longest:normalize=0[aout]"));
+        assert!(!graph.contains("[1:a]"));
+        assert!(has_audio);
+
+        let silent = vec![TrackLayer { spans: vec![(0.0, 4.0)], has_audio: false }; 2];
+        let (graph, has_audio) = build_layer_graph(&silent);
+        assert!(!graph.contains("amix") && !has_audio);
+    }
+
+    #[test]
+    fn test_timeline_round_trip() {
+        let timeline = Timeline::from_clips(vec![clip("a", 0, 1.0, 5.0)]);
+        let json = serde_json::to_value(&timeline).unwrap();
+        assert_eq!(json["tracks"][0]["items"][0]["type"], "gap");
+        assert_eq!(json["tracks"][0]["items"][1]["type"], "clip");
+        assert_eq!(json["tracks"][0]["items"][1]["trimEnd"], 4.0);
+        let parsed: Timeline = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, timeline);
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::transcription::{
+        parse_whisper_cpp_json, Transcript, TranscriptSegment, TranscriptWord, TranscriptionSettings,
+        WhisperCppBackend,
+    };
+    use std::path::{Path, PathBuf};
+
+    fn word(start: f64, end: f64, text: &str) -> TranscriptWord {
+        TranscriptWord { start, end, text: text.to_string() }
+    }
+
+    #[test]
+    fn test_parse_whisper_cpp_json_joins_tokens_into_words() {
+        let json = r#"{
+            "result": {"language": "en"},
+            "transcription": [
+                {
+                    "timestamps": {"from": "00:00:00,000", "to": "00:00:02,000"},
+                    "offsets": {"from": 0, "to": 2000},
+                    "text": " Hello wonderful world.",
+                    "tokens": [
+                        {"text": "[_BEG_]", "offsets": {"from": 0, "to": 0}, "id": 50364, "p": 0.9},
+                        {"text": " Hello", "offsets": {"from": 0, "to": 500}, "id": 1, "p": 0.9},
+                        {"text": " wonder", "offsets": {"from": 500, "to": 900}, "id": 2, "p": 0.9},
+                        {"text": "ful", "offsets": {"from": 900, "to": 1100}, "id": 3, "p": 0.9},
+                        {"text": " world", "offsets": {"from": 1100, "to": 1800}, "id": 4, "p": 0.9},
+                        {"text": ".", "offsets": {"from": 1800, "to": 2000}, "id": 5, "p": 0.9},
+                        {"text": "[_TT_100]", "offsets": {"from": 2000, "to": 2000}, "id": 50464, "p": 0.9}
+                    ]
+                },
+                {"offsets": {"from": 2000, "to": 2500}, "text": " ", "tokens": []}
+            ]
+        }"#;
+
+        let transcript = parse_whisper_cpp_json(json).unwrap();
+        assert_eq!(transcript.language, "en");
+        assert_eq!(transcript.segments.len(), 1);
+        assert_eq!(transcript.segments[0].text, "Hello wonderful world.");
+        assert_eq!(
+            transcript.segments[0].words,
+            vec![word(0.0, 0.5, "Hello"), word(0.5, 1.1, "wonderful"), word(1.1, 2.0, "world.")]
+        );
+        assert!(parse_whisper_cpp_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_vtt_with_word_timestamps() {
+        let transcript = Transcript {
+            language: "en".to_string(),
+            segments: vec![TranscriptSegment {
+                start: 0.0,
+                end: 2.0,
+                text: "Hello world".to_string(),
+                words: vec![word(0.0, 0.5, "Hello"), word(1.25, 2.0, "world")],
+            }],
+        };
+
+        assert_eq!(
+            transcript.to_vtt(true),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:02.000\nHello <00:00:01.250>world\n"
+        );
+        assert_eq!(transcript.to_vtt(false), "WEBVTT\n\n00:00:00.000 --> 00:00:02.000\nHello world\n");
+    }
+
+    #[test]
+    fn test_whisper_cpp_args_are_cpu_only() {
+        let backend = WhisperCppBackend {
+            binary: PathBuf::from("whisper-cli"),
+            model: PathBuf::from("/models/ggml-base.en.bin"),
+            threads: Some(4),
+        };
+        let args = backend.args(Path::new("/audio/clip.wav"), Path::new("/audio/clip.whisper"), None);
+
+        assert_eq!(
+            args,
+            vec![
+                "-m", "/models/ggml-base.en.bin", "-f", "/audio/clip.wav", "-l", "auto", "-ng", "-np", "-ojf",
+                "-of", "/audio/clip.whisper", "-t", "4",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_settings_select_backend() {
+        // Hosted transcription runs in the frontend, so the backend only accepts whisper.cpp
+        assert!(serde_json::from_str::<TranscriptionSettings>(r#"{"backend": "remote", "api_key": "key"}"#).is_err());
+
+        let missing_model: TranscriptionSettings =
+            serde_json::from_str(r#"{"backend": "whisper_cpp", "model_path": "/nonexistent/model.bin"}"#).unwrap();
+        assert!(missing_model.backend().is_err());
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::transitions::{
+        build_transition_graph, clip_offsets, timeline_duration, validate_transitions, Transition, TransitionKind,
+    };
+
+    fn transition(after_clip: usize, kind: TransitionKind, duration: f64) -> Transition {
+        Transition { after_clip, kind, duration }
+    }
+
+    #[test]
+    fn test_timeline_duration_subtracts_overlaps() {
+        let durations = [5.0, 4.0, 6.0];
+        let transitions = [transition(0, TransitionKind::Crossfade, 1.0)];
+
+        assert_eq!(timeline_duration(&durations, &transitions), 14.0);
+        assert_eq!(timeline_duration(&durations, &[]), 15.0);
+        assert_eq!(clip_offsets(&durations, &transitions), vec![0.0, 4.0, 8.0]);
+    }
+
+    #[test]
+    fn test_graph_mixes_transitions_and_cuts() {
+        let durations = [5.0, 4.0, 6.0];
+        let transitions = [transition(1, TransitionKind::DipToBlack, 0.5)];
+        let graph = build_transition_graph(&durations, &[true; 3], &transitions);
+
+        assert!(graph.contains("[0:v]fps=30,settb=AVTB,format=yuv420p[v0]"));
+        assert!(graph.contains("[v0][a0][v1][a1]concat=n=2:v=1:a=1[vx0][ax0]"));
+        assert!(graph.contains("[vx0][v2]xfade=transition=fadeblack:duration=0.500:offset=8.500[vout]"));
+        assert!(graph.contains("[ax0][a2]acrossfade=d=0.500[aout]"));
+    }
+
+    #[test]
+    fn test_graph_fills_missing_audio_with_silence() {
+        let durations = [5.0, 4.0];
+        let transitions = [transition(0, TransitionKind::Crossfade, 1.0)];
+        let graph = build_transition_graph(&durations, &[true, false], &transitions);
+
+        // Real audio is padded/cut to the video duration so acrossfade offsets match xfade
+        assert!(graph.contains("[0:a]aformat=sample_rates=48000:channel_layouts=stereo,asetpts=PTS-STARTPTS,apad,atrim=duration=5.000[a0]"));
+        assert!(graph.contains("anullsrc=channel_layout=stereo:sample_rate=48000,atrim=duration=4.000[a1]"));
+        assert!(!graph.contains("[1:a]"));
+        assert!(graph.contains("[a0][a1]acrossfade=d=1.000[aout]"));
+    }
+
+    #[test]
+    fn test_graph_chains_offsets() {
+        let durations = [5.0, 4.0, 6.0];
+        let transitions = [
+            transition(0, TransitionKind::Crossfade, 1.0),
+            transition(1, TransitionKind::WipeLeft, 1.0),
+        ];
+        let graph = build_transition_graph(&durations, &[true; 3], &transitions);
+
+        assert!(graph.contains("[v0][v1]xfade=transition=fade:duration=1.000:offset=4.000[vx0]"));
+        assert!(graph.contains("[vx0][v2]xfade=transition=wipeleft:duration=1.000:offset=7.000[vout]"));
+    }
+
+    #[test]
+    fn test_validate_transitions() {
+        let durations = [5.0, 1.5, 6.0];
+
+        assert!(validate_transitions(&durations, &[transition(0, TransitionKind::SlideUp, 1.0)]).is_ok());
+        assert!(validate_transitions(&durations, &[transition(2, TransitionKind::Crossfade, 1.0)]).is_err());
+        assert!(validate_transitions(&durations, &[transition(0, TransitionKind::Crossfade, 0.0)]).is_err());
+        assert!(validate_transitions(&durations, &[
+            transition(0, TransitionKind::Crossfade, 1.0),
+            transition(0, TransitionKind::WipeUp, 1.0),
+        ]).is_err());
+
+        // Middle clip can't fit a 1s transition on each edge
+        assert!(validate_transitions(&durations, &[
+            transition(0, TransitionKind::Crossfade, 1.0),
+            transition(1, TransitionKind::Crossfade, 1.0),
+        ]).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::process::Command;
+
+    use clipforge_lib::utils::trim::{
+        join_builder, plan_smart_cut, segment_builder, split_ranges, CutSegment, SourceVideo, TrimMode,
+        SOURCE_VIDEO_ENTRIES, SMART_PART_EXTENSION,
+    };
+
+    fn segment(start: f64, end: f64, copy: bool) -> CutSegment {
+        CutSegment { start, end, copy }
+    }
+
+    #[test]
+    fn test_trim_mode_serde() {
+        assert_eq!(TrimMode::default(), TrimMode::Fast);
+        assert_eq!(serde_json::to_string(&TrimMode::Accurate).unwrap(), "\"accurate\"");
+        let mode: TrimMode = serde_json::from_str("\"reencode\"").unwrap();
+        assert_eq!(mode, TrimMode::Reencode);
+    }
+
+    #[test]
+    fn test_smart_cut_reencodes_partial_gops() {
+        let keyframes = [0.0, 2.0, 4.0, 6.0, 8.0];
+        assert_eq!(
+            plan_smart_cut(&keyframes, 1.5, 7.25),
+            vec![segment(1.5, 2.0, false), segment(2.0, 6.0, true), segment(6.0, 7.25, false)]
+        );
+    }
+
+    #[test]
+    fn test_smart_cut_on_keyframes_is_pure_copy() {
+        let keyframes = [0.0, 2.0, 4.0, 6.0];
+        assert_eq!(plan_smart_cut(&keyframes, 2.0, 6.0), vec![segment(2.0, 6.0, true)]);
+        // Within tolerance of a keyframe counts as aligned
+        assert_eq!(plan_smart_cut(&keyframes, 1.9995, 4.0), vec![segment(2.0, 4.0, true)]);
+    }
+
+    #[test]
+    fn test_smart_cut_without_full_gop_reencodes_everything() {
+        let keyframes = [0.0, 2.0, 4.0];
+        assert_eq!(plan_smart_cut(&keyframes, 2.5, 3.5), vec![segment(2.5, 3.5, false)]);
+        assert_eq!(plan_smart_cut(&keyframes, 1.0, 3.0), vec![segment(1.0, 3.0, false)]);
+        assert_eq!(plan_smart_cut(&[], 1.0, 3.0), vec![segment(1.0, 3.0, false)]);
+    }
+
+    const PROBED: &str = "codec_name=h264\nprofile=Main\nlevel=31\npix_fmt=yuv420p\nr_frame_rate=30000/1001\ntime_base=1/30000\n";
+
+    #[test]
+    fn test_source_video_encoder_args() {
+        let source = SourceVideo::parse(PROBED).unwrap();
+        assert_eq!(source.timescale.as_deref(), Some("30000"));
+        assert_eq!(
+            source.matching_encoder_args().unwrap().join(" "),
+            "-c:v libx264 -preset medium -crf 18 -profile:v main -level:v 3.1 -pix_fmt yuv420p -r 30000/1001"
+        );
+
+        let ten_bit = SourceVideo { profile: "High 10".to_string(), pix_fmt: "yuv420p10le".to_string(), ..source.clone() };
+        assert_eq!(ten_bit.matching_encoder_args(), None);
+        let hevc = SourceVideo { codec: "hevc".to_string(), ..source };
+        assert_eq!(hevc.matching_encoder_args(), None);
+        assert_eq!(SourceVideo::parse("profile=Main\n"), None);
+    }
+
+    #[test]
+    fn test_segment_and_join_args() {
+        let source = SourceVideo::parse(PROBED).unwrap();
+        let encoder = source.matching_encoder_args().unwrap();
+
+        let copy = segment_builder("in.mp4", &segment(2.0, 6.0, true), &encoder, "part1.ts").build_args().unwrap().join(" ");
+        assert!(copy.contains("-c:v copy -c:a aac -bsf:v h264_mp4toannexb -f mpegts -y part1.ts"));
+
+        let head = segment_builder("in.mp4", &segment(1.5, 2.0, false), &encoder, "part0.ts").build_args().unwrap().join(" ");
+        assert!(head.contains("-c:a aac -c:v libx264 -preset medium -crf 18 -profile:v main"));
+        assert!(head.ends_with("-f mpegts -y part0.ts"));
+
+        let join = join_builder("list.txt", &source, "out.mp4").build_args().unwrap().join(" ");
+        assert!(join.contains("-f concat -safe 0 -i list.txt"));
+        assert!(join.ends_with("-c copy -avoid_negative_ts make_zero -video_track_timescale 30000 -y out.mp4"));
+    }
+
+    fn run(program: &str, args: &[String]) -> std::process::Output {
+        let output = Command::new(program).args(args).output().unwrap();
+        assert!(output.status.success(), "{} failed: {}", program, String::from_utf8_lossy(&output.stderr));
+        output
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    // Smart render a source encoded with different x264 settings than ours, then
+    // decode the joined file. Needs ffmpeg and ffprobe on PATH; skipped otherwise.
+    #[test]
+    fn test_smart_cut_join_decodes_cleanly() {
+        if Command::new("ffmpeg").arg("-version").output().is_err() {
+            eprintln!("ffmpeg not found on PATH, skipping smart cut decode test");
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+        let source_path = path("source.mp4");
+
+        // One keyframe per second; ultrafast disables CABAC so the PPS differs from our encoder's
+        run("ffmpeg", &strings(&[
+            "-v", "error", "-f", "lavfi", "-i", "testsrc2=size=320x240:rate=25", "-f", "lavfi", "-i", "sine=frequency=440",
+            "-t", "6", "-c:v", "libx264", "-preset", "ultrafast", "-profile:v", "main", "-pix_fmt", "yuv420p",
+            "-x264-params", "keyint=25:min-keyint=25:scenecut=0", "-c:a", "aac", "-y", &source_path,
+        ]));
+
+        let probed = run("ffprobe", &strings(&[
+            "-v", "error", "-select_streams", "v:0", "-show_entries", SOURCE_VIDEO_ENTRIES,
+            "-of", "default=noprint_wrappers=1", &source_path,
+        ]));
+        let source = SourceVideo::parse(&String::from_utf8_lossy(&probed.stdout)).unwrap();
+        let encoder = source.matching_encoder_args().unwrap();
+
+        let keyframes = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let segments = plan_smart_cut(&keyframes, 0.4, 4.6);
+        assert_eq!(segments.len(), 3);
+
+        let mut list = String::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let part = path(&format!("part{}.{}", i, SMART_PART_EXTENSION));
+            run("ffmpeg", &segment_builder(&source_path, segment, &encoder, &part).build_args().unwrap());
+            list.push_str(&format!("file '{}'\n", part));
+        }
+        let list_path = path("list.txt");
+        std::fs::write(&list_path, list).unwrap();
+        let output_path = path("joined.mp4");
+        run("ffmpeg", &join_builder(&list_path, &source, &output_path).build_args().unwrap());
+        assert!(Path::new(&output_path).exists());
+
+        // Any corrupt frame at a piece boundary shows up as a decode error
+        let decoded = run("ffmpeg", &strings(&["-v", "error", "-xerror", "-i", &output_path, "-f", "null", "-"]));
+        assert!(String::from_utf8_lossy(&decoded.stderr).trim().is_empty());
+
+        let counted = run("ffprobe", &strings(&[
+            "-v", "error", "-select_streams", "v:0", "-count_frames", "-show_entries", "stream=nb_read_frames",
+            "-of", "csv=p=0", &output_path,
+        ]));
+        let frames: i64 = String::from_utf8_lossy(&counted.stdout).trim().parse().unwrap();
+        assert!((frames - 105).abs() <= 2, "expected about 105 frames, got {}", frames);
+    }
+
+    #[test]
+    fn test_split_ranges() {
+        assert_eq!(split_ranges((0.0, 10.0), 4.0).unwrap(), [(0.0, 4.0), (4.0, 10.0)]);
+        assert_eq!(split_ranges((2.0, 8.0), 5.0).unwrap(), [(2.0, 5.0), (5.0, 8.0)]);
+        assert!(split_ranges((0.0, 10.0), 0.05).is_err());
+        assert!(split_ranges((0.0, 10.0), 9.95).is_err());
+        assert!(split_ranges((2.0, 8.0), 9.0).is_err());
+        assert!(split_ranges((0.0, 10.0), f64::NAN).is_err());
+    }
+}
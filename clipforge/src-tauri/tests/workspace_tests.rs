@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use clipforge_lib::utils::workspace::{
+        load, migrate, parse_current, stored_version, LoadedWorkspace, Workspace, WORKSPACE_VERSION,
+    };
+    use serde_json::json;
+
+    const V1_LEGACY: &str = include_str!("fixtures/workspace_v1_legacy.json");
+    const V1: &str = include_str!("fixtures/workspace_v1.json");
+    const V2: &str = include_str!("fixtures/workspace_v2.json");
+
+    #[test]
+    fn test_stored_version() {
+        assert_eq!(stored_version(&json!({ "clips": [] })).unwrap(), 1);
+        assert_eq!(stored_version(&json!({ "version": 2 })).unwrap(), 2);
+        assert!(stored_version(&json!({ "version": 0 })).is_err());
+        assert!(stored_version(&json!({ "version": "2" })).is_err());
+    }
+
+    #[test]
+    fn test_load_legacy_v1_fills_trims_and_tracks() {
+        let loaded = load(V1_LEGACY).unwrap();
+        assert_eq!(loaded.stored_version, 1);
+        assert!(loaded.was_migrated());
+
+        let workspace = loaded.workspace;
+        assert_eq!(workspace.version, WORKSPACE_VERSION);
+        assert_eq!(workspace.clips.len(), 2);
+        assert_eq!(workspace.clips[0].track, 0);
+        assert_eq!((workspace.clips[0].trim_start, workspace.clips[0].trim_end), (0.0, 12.5));
+        // Timeline span 7.5s of a 30s source
+        assert_eq!((workspace.clips[1].trim_start, workspace.clips[1].trim_end), (0.0, 7.5));
+        assert_eq!(workspace.selected_clip_id.as_deref(), Some("clip-2"));
+        assert_eq!(workspace.zoom, 12.0);
+    }
+
+    #[test]
+    fn test_load_v1_keeps_existing_fields() {
+        let workspace = load(V1).unwrap().workspace;
+        let intro = &workspace.clips[0];
+        assert_eq!((intro.trim_start, intro.trim_end), (2.0, 10.0));
+        assert_eq!(intro.effects.volume, Some(0.8));
+        assert_eq!(intro.media.codec.as_deref(), Some("h264"));
+        assert_eq!(intro.captions_path(), Some("/Users/demo/Library/Application Support/com.clipforge.app/clips/intro.vtt"));
+        assert_eq!(intro.transcription.as_ref().unwrap().segments.len(), 1);
+        assert_eq!(workspace.clips[1].track, 1);
+        assert_eq!(workspace.selected_clip_id, None);
+    }
+
+    #[test]
+    fn test_load_current_version_is_unchanged() {
+        let loaded = load(V2).unwrap();
+        assert_eq!(loaded.stored_version, 2);
+        assert!(!loaded.was_migrated());
+
+        let clip = &loaded.workspace.clips[0];
+        assert_eq!(clip.effects.speed, Some(2.0));
+        assert_eq!(clip.output_duration(), 4.0);
+        assert_eq!(clip.effects.color.as_ref().unwrap().saturation, Some(1.2));
+    }
+
+    #[test]
+    fn test_upgraded_workspace_round_trips() {
+        for fixture in [V1_LEGACY, V1, V2] {
+            let workspace = load(fixture).unwrap().workspace;
+            let saved = serde_json::to_string(&workspace).unwrap();
+            let reloaded = load(&saved).unwrap();
+            assert!(!reloaded.was_migrated());
+            assert_eq!(reloaded.workspace, workspace);
+        }
+    }
+
+    #[test]
+    fn test_rejects_newer_and_malformed_workspaces() {
+        let newer = format!("{{ \"version\": {}, \"clips\": [] }}", WORKSPACE_VERSION + 1);
+        assert!(load(&newer).unwrap_err().contains("newer version"));
+        assert!(load("[]").is_err());
+        assert!(load("{ \"clips\": ").is_err());
+
+        // An unreadable file is replaced by a fresh workspace that reports why
+        let fresh = LoadedWorkspace::replacing_unreadable("Workspace is not valid JSON".to_string());
+        assert_eq!(fresh.workspace, Workspace::default());
+        assert_eq!(fresh.warnings, vec!["Workspace is not valid JSON"]);
+        assert!(!fresh.was_migrated());
+    }
+
+    #[test]
+    fn test_load_skips_bad_clips_with_warnings() {
+        let mut state: serde_json::Value = serde_json::from_str(V2).unwrap();
+        let good = state["clips"][0].clone();
+        let mut bad = good.clone();
+        bad["id"] = json!("broken");
+        bad["start"] = json!("soon");
+        state["clips"] = json!([bad, "not a clip", good]);
+        state["selected_clip_id"] = json!("broken");
+
+        let loaded = load(&state.to_string()).unwrap();
+        assert_eq!(loaded.workspace.clips, vec![serde_json::from_value(good).unwrap()]);
+        assert_eq!(loaded.workspace.selected_clip_id, None);
+        assert_eq!(loaded.warnings.len(), 2);
+        assert!(loaded.warnings[0].starts_with("Skipped clip broken:"));
+        assert!(loaded.warnings[1].starts_with("Skipped clip #2:"));
+
+        // Bad settings are reset without dropping the clips
+        state["clips"] = json!([]);
+        state["zoom"] = json!("wide");
+        let loaded = load(&state.to_string()).unwrap();
+        assert_eq!(loaded.workspace.zoom, Workspace::default().zoom);
+        assert_eq!(loaded.warnings.len(), 1);
+
+        let loaded = load("{ \"clips\": 3 }").unwrap();
+        assert!(loaded.workspace.clips.is_empty());
+        assert_eq!(loaded.warnings.len(), 1);
+
+        // Migration leaves unreadable v1 clips for `load` to report
+        assert!(migrate(json!({ "clips": ["not a clip"] })).is_ok());
+        assert_eq!(load(r#"{ "clips": ["not a clip"] }"#).unwrap().warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_current_stamps_version() {
+        let workspace = parse_current(r#"{ "clips": [], "playhead": 2.0 }"#).unwrap();
+        assert_eq!(workspace, Workspace { playhead: 2.0, ..Default::default() });
+        assert!(parse_current(r#"{ "version": 1, "clips": [] }"#).is_err());
+    }
+}
@@ -87,30 +87,19 @@ export function ExportButton() {
         ? outputPath
         : `${outputPath}.mp4`
 
-      // Sort clips by timeline position (start time)
-      const sortedClips = [...validClips].sort((a, b) => a.start - b.start)
-
-      // Prepare clips with trim, audio, and caption information
-      const clipsWithTrim = sortedClips.map(c => ({
-        path: c.path,
-        trim_start: c.trimStart,
-        trim_end: c.trimEnd,
-        volume: c.volume,
-        muted: c.muted,
-        vtt_path: c.transcription?.vttPath
-      }))
-
-      console.log("[ClipForge] Exporting clips:", clipsWithTrim)
+      // The backend builds the timeline (tracks, gaps, effects) from the clips
+      // as stored and rejects it if clips overlap, over-trim or lost their media
+      console.log("[ClipForge] Exporting clips:", validClips)
       console.log("[ClipForge] Output path:", finalPath)
       console.log("[ClipForge] Resolution:", resolution)
-      console.log("[ClipForge] Total clips:", clipsWithTrim.length)
+      console.log("[ClipForge] Total clips:", validClips.length)
 
       // Reset real progress
       setRealProgress(0)
 
       // Invoke backend export
       const result = await invoke<string>("export_video", {
-        clips: clipsWithTrim,
+        clips: validClips,
         outputPath: finalPath,
         resolution,
       })
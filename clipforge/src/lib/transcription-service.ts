@@ -20,6 +20,44 @@ export interface TranscriptionResult {
   language: string;
 }
 
+/**
+ * Backend settings for transcription in the Rust backend (`transcribe_media`).
+ * Hosted transcription goes through TranscriptionService instead.
+ */
+export type BackendTranscriptionSettings =
+  { backend: 'whisper_cpp'; model_path: string; binary_path?: string; threads?: number };
+
+interface BackendTranscriptionOutput {
+  text: string;
+  segments: TranscriptionSegment[];
+  vtt_path: string;
+  language: string;
+}
+
+/**
+ * Transcribe a video in the Rust backend (e.g. offline with whisper.cpp).
+ * Writes a VTT with word-level timestamps next to the video.
+ */
+export async function transcribeWithBackend(
+  videoPath: string,
+  settings: BackendTranscriptionSettings,
+  language?: string
+): Promise<TranscriptionResult> {
+  const output = await invoke<BackendTranscriptionOutput>('transcribe_media', {
+    videoPath,
+    settings,
+    language,
+    wordTimestamps: true,
+  });
+
+  return {
+    text: output.text,
+    segments: output.segments.map(({ start, end, text }) => ({ start, end, text })),
+    vttPath: output.vtt_path,
+    language: output.language,
+  };
+}
+
 export class TranscriptionService {
   private openai: OpenAI;
   private progressCallback?: (progress: TranscriptionProgress) => void;
@@ -78,7 +116,7 @@ export class TranscriptionService {
   private async extractAudio(videoPath: string): Promise<string> {
     try {
       console.log('[Transcription] Extracting audio from:', videoPath);
-      const audioPath = await invoke<string>('extract_audio', { videoPath });
+      const { path: audioPath } = await invoke<{ path: string; duration: number }>('extract_audio', { videoPath });
       console.log('[Transcription] Audio extracted to:', audioPath);
       return audioPath;
     } catch (error) {
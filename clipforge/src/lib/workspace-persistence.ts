@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core"
 import type { Clip } from "../types/clip"
 
 interface WorkspaceState {
+  version?: number // Schema version, set by the backend on save and upgraded on load
   clips: Clip[]
   playhead: number
   is_playing: boolean
@@ -163,26 +164,36 @@ function validateWorkspaceState(state: any): WorkspaceState {
   }
 }
 
+interface LoadedWorkspace {
+  workspace: WorkspaceState
+  stored_version: number
+  warnings: string[] // Clips or settings the backend had to drop
+}
+
 /**
- * Load workspace from persistent storage with validation
+ * Load workspace from persistent storage with validation.
+ * Returns null when nothing has been saved yet and throws when the saved file
+ * can't be read, so the caller can avoid overwriting it.
  */
-export async function loadWorkspace(): Promise<WorkspaceState | null> {
-  try {
-    const stateJson = await invoke<string>("load_workspace")
-    const rawState = JSON.parse(stateJson)
-    const validatedState = validateWorkspaceState(rawState)
-
-    console.log("[workspace] Loaded and validated workspace", {
-      clipsCount: validatedState.clips.length,
-      playhead: validatedState.playhead,
-      zoom: validatedState.zoom,
-    })
-
-    return validatedState
-  } catch (error) {
-    console.warn("[workspace] No workspace to load or error loading:", error)
+export async function loadWorkspace(): Promise<{ state: WorkspaceState; warnings: string[] } | null> {
+  // The backend migrates older workspace files to the current schema
+  const loaded = await invoke<LoadedWorkspace | null>("load_workspace")
+  if (!loaded) {
+    console.log("[workspace] No saved workspace")
     return null
   }
+  const validatedState = validateWorkspaceState(loaded.workspace)
+
+  if (loaded.warnings.length > 0) {
+    console.warn("[workspace] Loaded with problems:", loaded.warnings)
+  }
+  console.log("[workspace] Loaded and validated workspace", {
+    clipsCount: validatedState.clips.length,
+    playhead: validatedState.playhead,
+    zoom: validatedState.zoom,
+  })
+
+  return { state: validatedState, warnings: loaded.warnings }
 }
 
 /**
@@ -1,7 +1,7 @@
 import { create } from "zustand"
 import { subscribeWithSelector } from "zustand/middleware"
 import { invoke } from "@tauri-apps/api/core"
-import type { Clip, Transcription } from "../types/clip"
+import type { Clip, SplitMode, Transcription, TrimMode, VideoMetadata } from "../types/clip"
 import { debouncedSaveWorkspace, loadWorkspace } from "../lib/workspace-persistence"
 
 interface ClipStore {
@@ -15,6 +15,7 @@ interface ClipStore {
   error: string | null
   exportProgress: number
   isHydrated: boolean
+  autosaveBlocked: boolean  // Set when the saved workspace couldn't be loaded, so it isn't overwritten
 
   addClip: (clip: Clip) => void
   updateClip: (id: string, updates: Partial<Clip>) => void
@@ -34,7 +35,9 @@ interface ClipStore {
   clearClips: () => void
   resetWorkspace: () => Promise<void>
   loadState: (state: Partial<ClipStore>) => void
-  trimClip: (id: string, start: number, end: number) => Promise<void>
+  trimClip: (id: string, start: number, end: number, mode?: TrimMode) => Promise<void>
+  splitClip: (id: string, time: number, mode?: SplitMode) => Promise<void>
+  splitClipAtScenes: (id: string) => Promise<void>
   hydrateFromWorkspace: () => Promise<void>
 }
 
@@ -50,6 +53,7 @@ export const useClipStore = create<ClipStore>()(
   error: null,
   exportProgress: 0,
   isHydrated: false,
+  autosaveBlocked: false,
 
   addClip: (clip) =>
     set((state) => ({
@@ -236,6 +240,7 @@ export const useClipStore = create<ClipStore>()(
         selectedClipId: null,
         error: null,
         exportProgress: 0,
+        autosaveBlocked: false,
       })
     } catch (err) {
       console.error('[ClipForge] Reset workspace failed:', err)
@@ -247,8 +252,9 @@ export const useClipStore = create<ClipStore>()(
 
   hydrateFromWorkspace: async () => {
     try {
-      const workspace = await loadWorkspace()
-      if (workspace) {
+      const loaded = await loadWorkspace()
+      if (loaded) {
+        const { state: workspace, warnings } = loaded
         set({
           clips: workspace.clips,
           playhead: workspace.playhead,
@@ -257,6 +263,7 @@ export const useClipStore = create<ClipStore>()(
           selectedClipId: workspace.selected_clip_id,
           exportProgress: workspace.export_progress,
           isHydrated: true,
+          error: warnings.length > 0 ? `Some of the saved workspace couldn't be restored: ${warnings.join("; ")}` : null,
         })
         console.log("[store] Hydrated from workspace")
       } else {
@@ -265,17 +272,35 @@ export const useClipStore = create<ClipStore>()(
       }
     } catch (error) {
       console.error("[store] Failed to hydrate from workspace:", error)
-      set({ isHydrated: true }) // Mark as hydrated even on error
+      // The backend moves an unreadable file aside; if it couldn't, don't autosave the empty store over it
+      set({
+        isHydrated: true,
+        autosaveBlocked: true,
+        error: `Couldn't load the saved workspace, so changes won't be saved this session: ${error}`,
+      })
     }
   },
 
 
 
-  trimClip: async (id, trimStart, trimEnd) => {
+  trimClip: async (id, trimStart, trimEnd, mode) => {
     const state = get()
     const clip = state.clips.find(c => c.id === id)
     if (!clip) return
 
+    // Without a mode the trim stays virtual: the clip keeps pointing at its
+    // source and the in/out points are only rendered at export
+    if (!mode) {
+      // The backend validates the range and accounts for speed
+      const trimmed = await invoke<Clip>('set_clip_trim', { clip, trimStart, trimEnd })
+      set((state) => ({
+        clips: state.clips.map(c => c.id === id ? trimmed : c),
+        playhead: Math.min(Math.max(state.playhead, trimmed.start), trimmed.end),
+      }))
+      console.log("[ClipForge] Applied virtual trim:", { id, trimStart, trimEnd })
+      return
+    }
+
     try {
       console.log("[ClipForge] Applying trim:", { id, trimStart, trimEnd, originalPath: clip.path })
 
@@ -289,6 +314,7 @@ export const useClipStore = create<ClipStore>()(
         outputPath,
         startTime: trimStart,
         endTime: trimEnd,
+        mode,
       })
 
       const newDuration = trimEnd - trimStart
@@ -334,6 +360,79 @@ export const useClipStore = create<ClipStore>()(
       throw err
     }
   },
+
+  splitClip: async (id, time, mode = 'virtual') => {
+    const state = get()
+    const clip = state.clips.find(c => c.id === id)
+    if (!clip || time <= clip.start || time >= clip.end) return
+
+    try {
+      // The backend maps timeline time to source time, honouring speed and reverse
+      let [first, second] = await invoke<[Clip, Clip]>('split_clip_at', {
+        clip,
+        time,
+        secondId: `${clip.id}-split-${Date.now()}`,
+      })
+
+      if (mode !== 'virtual') {
+        // Files are cut in source order; a reversed clip plays the later range first
+        const reversed = first.trimStart > second.trimStart
+        const [early, late] = reversed ? [second, first] : [first, second]
+        const result = await invoke<{ first: VideoMetadata; second: VideoMetadata; first_start: number; second_start: number }>('split_clip', {
+          inputPath: clip.path,
+          trimStart: clip.trimStart,
+          trimEnd: clip.trimEnd,
+          splitTime: early.trimEnd,
+          mode,
+        })
+
+        // Each file starts at first_start/second_start in the old source
+        const withFile = (half: Clip, file: VideoMetadata, fileStart: number): Clip => ({
+          ...half,
+          path: file.file_path,
+          duration: file.duration,
+          thumbnail_path: file.thumbnail_path,
+          file_size: file.file_size,
+          trimStart: half.trimStart - fileStart,
+          trimEnd: half.trimEnd - fileStart,
+        })
+        const earlyPart = withFile(early, result.first, result.first_start)
+        const latePart = withFile(late, result.second, result.second_start)
+        first = reversed ? latePart : earlyPart
+        second = reversed ? earlyPart : latePart
+      }
+
+      set((state) => ({
+        clips: state.clips.flatMap(c => c.id === id ? [first, second] : [c]),
+        selectedClipId: second.id,
+      }))
+      console.log("[ClipForge] Split clip:", { id, time, mode })
+    } catch (err) {
+      console.error('[ClipForge] Split failed:', err)
+      throw err
+    }
+  },
+
+  splitClipAtScenes: async (id) => {
+    const clip = get().clips.find(c => c.id === id)
+    if (!clip) return
+
+    try {
+      // Virtual split: each scene becomes a sub-clip trimmed from the same source
+      const parts = await invoke<Clip[]>('split_clip_at_scenes', {
+        clip,
+        idPrefix: `${clip.id}-scene-${Date.now()}`,
+      })
+
+      set((state) => ({
+        clips: state.clips.flatMap(c => c.id === id ? parts : [c]),
+      }))
+      console.log("[ClipForge] Split clip at scenes:", { id, scenes: parts.length })
+    } catch (err) {
+      console.error('[ClipForge] Scene split failed:', err)
+      throw err
+    }
+  },
 })))
 
 // Auto-save workspace when relevant state changes
@@ -348,7 +447,8 @@ useClipStore.subscribe(
   }),
   (workspace) => {
     // Only save if the store has been hydrated to avoid overwriting on initial load
-    if (useClipStore.getState().isHydrated) {
+    const { isHydrated, autosaveBlocked } = useClipStore.getState()
+    if (isHydrated && !autosaveBlocked) {
       debouncedSaveWorkspace(workspace)
     }
   },
@@ -32,6 +32,18 @@ export interface Clip {
   transcription?: Transcription // AI-generated transcription with captions
 }
 
+/**
+ * How trim_clip cuts: stream copy (fast, keyframe aligned), smart render
+ * (frame accurate, re-encodes only around the cut points) or full re-encode
+ */
+export type TrimMode = 'fast' | 'accurate' | 'reencode'
+
+/**
+ * How splitClip cuts: 'virtual' keeps both halves on the same file with
+ * different trims, the trim modes render each half to a new library file
+ */
+export type SplitMode = TrimMode | 'virtual'
+
 export interface VideoMetadata {
   duration: number
   width: number